@@ -1,6 +1,8 @@
 const_eval_address_space_full =
     there are no more free addresses in the address space
 const_eval_align_check_failed = accessing memory with alignment {$has}, but alignment {$required} is required
+const_eval_align_check_repeated =
+    this access occurred {$times} times during the evaluation, but is only reported once
 const_eval_align_offset_invalid_align =
     `align_offset` called with non-power-of-two align: {$target_align}
 
@@ -38,6 +40,9 @@ const_eval_const_context = {$kind ->
     *[other] {""}
 }
 
+const_eval_const_slice_index_out_of_bounds =
+    index out of bounds: the index is {$index} but the const slice has length {$len}
+
 const_eval_copy_nonoverlapping_overlapping =
     `copy_nonoverlapping` called on overlapping ranges
 
@@ -77,12 +82,14 @@ const_eval_different_allocations =
     `{$name}` called on pointers into different allocations
 
 const_eval_division_by_zero =
-    dividing by zero
+    dividing `{$dividend}` by zero
 const_eval_division_overflow =
     overflow in signed division (dividing MIN by -1)
 const_eval_double_storage_live =
     StorageLive on a local that was already live
 
+const_eval_dump_const_deps_failed = failed to write `-Z dump-const-deps` output to `{$path}`: {$error}
+
 const_eval_dyn_call_not_a_method =
     `dyn` call trying to call something that is not a method
 
@@ -111,6 +118,10 @@ const_eval_fn_ptr_call =
 const_eval_for_loop_into_iter_non_const =
     cannot convert `{$ty}` into an iterator in {const_eval_const_context}s
 
+const_eval_forbidden_const_bytes =
+    the final value of `{$name}` contains the forbidden bytes `{$pattern}`
+    .note = found in {$alloc_id} at offset {$offset}, as requested by `-Z forbid-const-bytes`
+
 const_eval_frame_note = {$times ->
     [0] {const_eval_frame_note_inner}
     *[other] [... {$times} additional calls {const_eval_frame_note_inner} ...]
@@ -218,6 +229,8 @@ const_eval_max_num_nodes_in_const = maximum number of nodes exceeded in constant
 const_eval_memory_access_test = memory access failed
 const_eval_memory_exhausted =
     tried to allocate more memory than available to compiler
+const_eval_memory_limit_exceeded =
+    evaluation of `{$item}` exceeded the memory limit of {$limit} bytes set by `-Z const-eval-max-memory`
 const_eval_modified_global =
     modifying a static's initial value from another static's initializer
 
@@ -226,6 +239,9 @@ const_eval_mut_deref =
 
 const_eval_mutable_ref_in_const = {$front_matter}: encountered mutable reference in a `const`
 const_eval_never_val = {$front_matter}: encountered a value of the never type `!`
+const_eval_non_canonical_const_generic = const generic argument is computed from literals
+    .suggestion = use the resulting literal instead
+
 const_eval_non_const_fmt_macro_call =
     cannot call non-const formatting macro in {const_eval_const_context}s
 
@@ -259,7 +275,7 @@ const_eval_operator_non_const =
     cannot call non-const operator in {const_eval_const_context}s
 const_eval_out_of_range = {$front_matter}: encountered {$value}, but expected something {$in_range}
 const_eval_overflow =
-    overflow executing `{$name}`
+    overflow executing `{$name}`: attempt to compute `{$left} {$op} {$right}`, which would overflow
 
 const_eval_overflow_shift =
     overflowing shift by {$val} in `{$name}`
@@ -328,9 +344,12 @@ const_eval_ref_to_mut = {$front_matter}: encountered a reference pointing to mut
 const_eval_ref_to_static = {$front_matter}: encountered a reference pointing to a static variable in a constant
 const_eval_ref_to_uninhabited = {$front_matter}: encountered a reference pointing to uninhabited type {$ty}
 const_eval_remainder_by_zero =
-    calculating the remainder with a divisor of zero
+    calculating the remainder of `{$dividend}` with a divisor of zero
 const_eval_remainder_overflow =
     overflow in signed remainder (dividing MIN by -1)
+const_eval_repeated_inline_const_eval =
+    this constant is evaluated separately for more than {$threshold} instances of its parent
+    .help = if it doesn't depend on generic parameters, consider moving it into a `const` item
 const_eval_scalar_size_mismatch =
     scalar size mismatch: expected {$target_size} bytes but got {$data_size} bytes instead
 const_eval_size_of_unsized =
@@ -411,6 +430,9 @@ const_eval_uninhabited_val = {$front_matter}: encountered a value of uninhabited
 const_eval_uninit = {$front_matter}: encountered uninitialized bytes
 const_eval_uninit_bool = {$front_matter}: encountered uninitialized memory, but expected a boolean
 const_eval_uninit_box = {$front_matter}: encountered uninitialized memory, but expected a box
+const_eval_uninit_bytes_in_final = encountered uninitialized bytes of type `{$ty}` in final constant
+    .note = `-Z const-check-uninit` requires all non-padding bytes outside of unions to be initialized
+
 const_eval_uninit_char = {$front_matter}: encountered uninitialized memory, but expected a unicode scalar value
 const_eval_uninit_enum_tag = {$front_matter}: encountered uninitialized bytes, but expected a valid enum tag
 const_eval_uninit_float = {$front_matter}: encountered uninitialized memory, but expected a floating point number
@@ -1,15 +1,25 @@
+use std::cell::RefCell;
 use std::mem;
 
-use rustc_errors::{DiagnosticArgValue, DiagnosticMessage, IntoDiagnostic, IntoDiagnosticArg};
+use rustc_data_structures::fx::FxIndexMap;
+
+use rustc_errors::{
+    DecorateLint, DiagnosticArgValue, DiagnosticMessage, IntoDiagnostic, IntoDiagnosticArg,
+};
+use rustc_hir::{HirId, CRATE_HIR_ID};
 use rustc_middle::mir::AssertKind;
 use rustc_middle::ty::TyCtxt;
 use rustc_middle::ty::{layout::LayoutError, ConstInt};
+use rustc_session::lint::builtin::INVALID_ALIGNMENT;
+use rustc_session::lint::Lint;
 use rustc_span::source_map::Spanned;
 use rustc_span::{ErrorGuaranteed, Span, Symbol};
 
 use super::InterpCx;
 use crate::errors::{self, FrameNote, ReportErrorExt};
-use crate::interpret::{ErrorHandled, InterpError, InterpErrorInfo, Machine, MachineStopType};
+use crate::interpret::{
+    ErrorHandled, GlobalId, InterpError, InterpErrorInfo, Machine, MachineStopType,
+};
 
 /// The CTFE machine has some custom error kinds.
 #[derive(Clone, Debug)]
@@ -19,6 +29,8 @@ pub enum ConstEvalErrKind {
     AssertFailure(AssertKind<ConstInt>),
     Panic { msg: Symbol, line: u32, col: u32, file: Symbol },
     Abort(String),
+    MemoryLimitExceeded { item: String, limit: u64 },
+    ConstSliceIndexOutOfBounds { index: u64, len: u64 },
 }
 
 impl MachineStopType for ConstEvalErrKind {
@@ -31,6 +43,8 @@ fn diagnostic_message(&self) -> DiagnosticMessage {
             Panic { .. } => const_eval_panic,
             AssertFailure(x) => x.diagnostic_message(),
             Abort(msg) => msg.to_string().into(),
+            MemoryLimitExceeded { .. } => const_eval_memory_limit_exceeded,
+            ConstSliceIndexOutOfBounds { .. } => const_eval_const_slice_index_out_of_bounds,
         }
     }
     fn add_args(
@@ -47,6 +61,14 @@ fn add_args(
                 adder("line".into(), line.into_diagnostic_arg());
                 adder("col".into(), col.into_diagnostic_arg());
             }
+            MemoryLimitExceeded { item, limit } => {
+                adder("item".into(), item.into_diagnostic_arg());
+                adder("limit".into(), limit.into_diagnostic_arg());
+            }
+            ConstSliceIndexOutOfBounds { index, len } => {
+                adder("index".into(), index.into_diagnostic_arg());
+                adder("len".into(), len.into_diagnostic_arg());
+            }
         }
     }
 }
@@ -115,6 +137,51 @@ pub fn get_span_and_frames<'tcx, 'mir, M: Machine<'mir, 'tcx>>(
     (span, frames)
 }
 
+/// Returns the node that lints about the evaluation of `cid` are attached to: the item the
+/// constant belongs to, or the crate root if that item is defined in another crate.
+pub fn lint_root_for_global_id<'tcx>(tcx: TyCtxt<'tcx>, cid: GlobalId<'tcx>) -> HirId {
+    cid.instance
+        .def_id()
+        .as_local()
+        .map_or(CRATE_HIR_ID, |def_id| tcx.hir().local_def_id_to_hir_id(def_id))
+}
+
+/// Reports a finding of the evaluation of `cid` through the lint system, for conditions that
+/// shouldn't be hard errors. Unlike `report`, this respects the lint level at the item the
+/// constant belongs to, so the finding can be allowed like any other lint.
+pub fn lint<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    lint: &'static Lint,
+    cid: GlobalId<'tcx>,
+    span: Span,
+    decorator: impl for<'a> DecorateLint<'a, ()>,
+) {
+    tcx.emit_spanned_lint(lint, lint_root_for_global_id(tcx, cid), span, decorator);
+}
+
+/// `INVALID_ALIGNMENT` lints found during a single evaluation. They are only emitted once the
+/// evaluation is done, deduplicated by their span and the alignments involved, so a misaligned
+/// access in a loop is reported once, with a note saying how often it happened. Other const-eval
+/// errors stop the evaluation, so they can't repeat within it.
+#[derive(Default)]
+pub struct PendingAlignmentLints {
+    lints: RefCell<FxIndexMap<(Span, u64, u64), (HirId, errors::AlignmentCheckFailed, usize)>>,
+}
+
+impl PendingAlignmentLints {
+    pub(super) fn add(&self, span: Span, lint_root: HirId, lint: errors::AlignmentCheckFailed) {
+        let mut lints = self.lints.borrow_mut();
+        lints.entry((span, lint.has, lint.required)).or_insert((lint_root, lint, 0)).2 += 1;
+    }
+
+    pub(super) fn emit(&self, tcx: TyCtxt<'_>) {
+        for ((span, ..), (lint_root, mut lint, times)) in self.lints.take() {
+            lint.repeated = (times > 1).then_some(errors::AlignmentCheckRepeated { times });
+            tcx.emit_spanned_lint(INVALID_ALIGNMENT, lint_root, span, lint);
+        }
+    }
+}
+
 /// Create a diagnostic for a const eval error.
 ///
 /// This will use the `mk` function for creating the error which will get passed labels according to
@@ -3,19 +3,28 @@
 
 use either::{Left, Right};
 
+use rustc_data_structures::fx::{FxHashSet, FxIndexSet};
+use rustc_errors::ErrorGuaranteed;
 use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
 use rustc_middle::mir;
-use rustc_middle::mir::interpret::{ErrorHandled, InterpErrorInfo};
-use rustc_middle::mir::pretty::write_allocation_bytes;
+use rustc_middle::mir::interpret::{AllocId, ConstShape, ErrorHandled, GlobalAlloc};
+use rustc_middle::mir::pretty::{display_allocation, write_allocation_bytes};
 use rustc_middle::traits::Reveal;
-use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::layout::{IntegerExt, LayoutOf};
 use rustc_middle::ty::print::with_no_trimmed_paths;
-use rustc_middle::ty::{self, TyCtxt};
+use rustc_middle::ty::{self, Ty, TyCtxt, TypeVisitableExt};
+use rustc_session::lint::builtin::REPEATED_INLINE_CONST_EVAL;
+use rustc_session::lint::Level;
 use rustc_span::source_map::Span;
+use rustc_span::symbol::Symbol;
 use rustc_target::abi::{self, Abi};
 
-use super::{CanAccessStatics, CompileTimeEvalContext, CompileTimeInterpreter};
-use crate::errors;
+use super::{
+    lint, lint_root_for_global_id, CanAccessStatics, CompileTimeEvalContext,
+    CompileTimeInterpreter, RecoveredUb, WrappedOverflow,
+};
+use crate::errors::{self, RepeatedInlineConstEval};
 use crate::interpret::eval_nullary_intrinsic;
 use crate::interpret::{
     intern_const_alloc_recursive, Allocation, ConstAlloc, ConstValue, CtfeValidationMode, GlobalId,
@@ -23,6 +32,8 @@
     RefTracking, StackPopCleanup,
 };
 
+use std::fmt::Write;
+
 // Returns a pointer to where the result lives
 fn eval_body_using_ecx<'mir, 'tcx>(
     ecx: &mut CompileTimeEvalContext<'mir, 'tcx>,
@@ -47,6 +58,9 @@ fn eval_body_using_ecx<'mir, 'tcx>(
     );
     let layout = ecx.layout_of(body.bound_return_ty().subst(tcx, cid.instance.substs))?;
     assert!(layout.is_sized());
+    // The return place is allocated before the root frame exists, so the machine does not
+    // enforce the memory limit for it on its own.
+    ecx.machine.check_memory_limit(tcx, cid.instance.def_id(), layout.size)?;
     let ret = ecx.allocate(layout, MemoryKind::Stack)?;
 
     trace!(
@@ -100,7 +114,7 @@ pub(super) fn mk_eval_cx<'mir, 'tcx>(
         tcx,
         root_span,
         param_env,
-        CompileTimeInterpreter::new(can_access_statics, CheckAlignment::No),
+        CompileTimeInterpreter::new(tcx, can_access_statics, CheckAlignment::No),
     )
 }
 
@@ -309,6 +323,7 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
         // Statics (and promoteds inside statics) may access other statics, because unlike consts
         // they do not have to behave "as if" they were evaluated at runtime.
         CompileTimeInterpreter::new(
+            tcx,
             CanAccessStatics::from(is_static),
             if tcx.sess.opts.unstable_opts.extra_const_ub_checks {
                 CheckAlignment::Error
@@ -318,8 +333,20 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
         ),
     );
 
+    if let Some(path) = &tcx.sess.opts.unstable_opts.trace_const_eval
+        && cid.promoted.is_none()
+        && tcx.def_path_str(def) == *path
+    {
+        ecx.machine.trace = true;
+    }
+
+    count_inline_const_eval(tcx, cid);
+    push_const_dep(tcx, cid);
     let res = ecx.load_mir(cid.instance.def, cid.promoted);
-    match res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body)) {
+    let res = res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body));
+    pop_const_dep(tcx);
+    ecx.machine.alignment_lints.emit(tcx);
+    match res {
         Err(error) => {
             let (error, backtrace) = error.into_parts();
             backtrace.print_backtrace();
@@ -354,23 +381,8 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
         }
         Ok(mplace) => {
             // Since evaluation had no errors, validate the resulting constant.
-            // This is a separate `try` block to provide more targeted error reporting.
-            let validation: Result<_, InterpErrorInfo<'_>> = try {
-                let mut ref_tracking = RefTracking::new(mplace);
-                let mut inner = false;
-                while let Some((mplace, path)) = ref_tracking.todo.pop() {
-                    let mode = match tcx.static_mutability(cid.instance.def_id()) {
-                        Some(_) if cid.promoted.is_some() => {
-                            // Promoteds in statics are allowed to point to statics.
-                            CtfeValidationMode::Const { inner, allow_static_ptrs: true }
-                        }
-                        Some(_) => CtfeValidationMode::Regular, // a `static`
-                        None => CtfeValidationMode::Const { inner, allow_static_ptrs: false },
-                    };
-                    ecx.const_validate_operand(&mplace.into(), path, &mut ref_tracking, mode)?;
-                    inner = true;
-                }
-            };
+            // This is separate from evaluation to provide more targeted error reporting.
+            let validation = validate_const(&ecx, cid, mplace);
             let alloc_id = mplace.ptr.provenance.unwrap();
 
             // Validation failed, report an error. This is always a hard error.
@@ -406,9 +418,435 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
                     },
                 ))
             } else {
+                if let Some(pattern) = &tcx.sess.opts.unstable_opts.forbid_const_bytes {
+                    check_forbidden_bytes(tcx, cid, alloc_id, pattern)?;
+                }
+                if let Some(path) = &tcx.sess.opts.unstable_opts.dump_const_alloc
+                    && cid.promoted.is_none()
+                    && tcx.def_path_str(def) == *path
+                {
+                    let alloc = tcx.global_alloc(alloc_id).unwrap_memory();
+                    eprintln!(
+                        "{path}: {alloc_id:?} ({}",
+                        display_allocation(tcx, alloc.inner())
+                    );
+                }
                 // Convert to raw constant
                 Ok(ConstAlloc { alloc_id, ty: mplace.layout.ty })
             }
         }
     }
 }
+
+/// Implements `-Z forbid-const-bytes`: emits an error if `pattern` occurs within the allocation
+/// `alloc_id` holding the value of `cid`, or within the memory it points to. Statics that the
+/// value points to are not scanned, and occurrences spanning two allocations are not detected.
+fn check_forbidden_bytes<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    cid: GlobalId<'tcx>,
+    alloc_id: AllocId,
+    pattern: &[u8],
+) -> Result<(), ErrorGuaranteed> {
+    let mut seen = FxHashSet::default();
+    let mut todo = vec![alloc_id];
+    while let Some(alloc_id) = todo.pop() {
+        if !seen.insert(alloc_id) {
+            continue;
+        }
+        let Some(GlobalAlloc::Memory(alloc)) = tcx.try_get_global_alloc(alloc_id) else {
+            continue;
+        };
+        if let Some(offset) = alloc.inner().find_bytes(pattern, &tcx) {
+            let pattern = pattern.iter().map(|byte| format!("{byte:02x}")).collect();
+            return Err(tcx.sess.emit_err(errors::ForbiddenConstBytes {
+                span: tcx.def_span(cid.instance.def_id()),
+                name: cid.display(tcx),
+                pattern,
+                alloc_id: format!("{alloc_id:?}"),
+                offset: offset.bytes(),
+            }));
+        }
+        todo.extend(alloc.inner().provenance().ptrs().iter().map(|&(_, alloc_id)| alloc_id));
+    }
+    Ok(())
+}
+
+/// How many instances of an anonymous or inline constant may be evaluated before the
+/// `repeated_inline_const_eval` lint fires.
+const REPEATED_INLINE_CONST_EVAL_THRESHOLD: usize = 64;
+
+/// Counts the evaluations of anonymous and inline constants, and fires the
+/// `repeated_inline_const_eval` lint once `cid` has been evaluated for too many instances. Nothing
+/// is counted where the lint is allowed.
+fn count_inline_const_eval<'tcx>(tcx: TyCtxt<'tcx>, cid: GlobalId<'tcx>) {
+    let def = cid.instance.def_id();
+    if cid.promoted.is_some()
+        || !def.is_local()
+        || !matches!(tcx.def_kind(def), DefKind::AnonConst | DefKind::InlineConst)
+    {
+        return;
+    }
+    let lint_root = lint_root_for_global_id(tcx, cid);
+    if tcx.lint_level_at_node(REPEATED_INLINE_CONST_EVAL, lint_root).0 == Level::Allow {
+        return;
+    }
+    let count = {
+        let mut counts = tcx.inline_const_eval_counts.lock();
+        let count = counts.entry(def).or_default();
+        *count += 1;
+        *count
+    };
+    if count == REPEATED_INLINE_CONST_EVAL_THRESHOLD + 1 {
+        let threshold = REPEATED_INLINE_CONST_EVAL_THRESHOLD;
+        lint(
+            tcx,
+            REPEATED_INLINE_CONST_EVAL,
+            cid,
+            tcx.def_span(def),
+            RepeatedInlineConstEval { threshold },
+        );
+    }
+}
+
+/// With `-Z dump-const-deps`, records that the evaluation of `cid` starts. The edge from the global
+/// that forced it is normally recorded at the call site already, but this also covers requests that
+/// didn't go through `TyCtxt::note_eval_to_allocation_raw` and friends.
+fn push_const_dep<'tcx>(tcx: TyCtxt<'tcx>, cid: GlobalId<'tcx>) {
+    if tcx.sess.opts.unstable_opts.dump_const_deps.is_none() {
+        return;
+    }
+    let mut deps = tcx.const_eval_deps.lock();
+    deps.record_forcing(cid);
+    deps.stack.push(cid);
+}
+
+/// With `-Z dump-const-deps`, records that the innermost evaluation has finished.
+fn pop_const_dep(tcx: TyCtxt<'_>) {
+    if tcx.sess.opts.unstable_opts.dump_const_deps.is_none() {
+        return;
+    }
+    tcx.const_eval_deps.lock().stack.pop();
+}
+
+/// With `-Z dump-const-deps`, writes the graph of which globals forced the evaluation of which
+/// others to the requested file, as DOT. Only evaluations that happened before this is called are
+/// included.
+pub fn dump_const_deps(tcx: TyCtxt<'_>) {
+    let Some(path) = &tcx.sess.opts.unstable_opts.dump_const_deps else {
+        return;
+    };
+    // Printing the globals may evaluate constants, so don't hold the lock while doing that.
+    let (nodes, edges) = {
+        let deps = tcx.const_eval_deps.lock();
+        (deps.nodes.clone(), deps.edges.clone())
+    };
+    let mut dot = String::from("digraph const_deps {\n");
+    for (i, node) in nodes.iter().enumerate() {
+        writeln!(dot, "    n{i} [label={:?}];", node.display(tcx)).unwrap();
+    }
+    for (parent, child) in &edges {
+        let parent = nodes.get_index_of(parent).unwrap();
+        let child = nodes.get_index_of(child).unwrap();
+        writeln!(dot, "    n{parent} -> n{child};").unwrap();
+    }
+    dot.push_str("}\n");
+    if let Err(error) = std::fs::write(path, dot) {
+        tcx.sess.emit_err(errors::DumpConstDepsFailed { path: path.clone(), error });
+    }
+}
+
+/// Checks that the final value `mplace` of the constant `cid`, and everything it points to, is
+/// valid for its type.
+fn validate_const<'mir, 'tcx>(
+    ecx: &CompileTimeEvalContext<'mir, 'tcx>,
+    cid: GlobalId<'tcx>,
+    mplace: MPlaceTy<'tcx>,
+) -> InterpResult<'tcx> {
+    let mut ref_tracking = RefTracking::new(mplace);
+    let mut inner = false;
+    while let Some((mplace, path)) = ref_tracking.todo.pop() {
+        let mode = match ecx.tcx.static_mutability(cid.instance.def_id()) {
+            Some(_) if cid.promoted.is_some() => {
+                // Promoteds in statics are allowed to point to statics.
+                CtfeValidationMode::Const { inner, allow_static_ptrs: true }
+            }
+            Some(_) => CtfeValidationMode::Regular, // a `static`
+            None => CtfeValidationMode::Const { inner, allow_static_ptrs: false },
+        };
+        ecx.const_validate_operand(&mplace.into(), path, &mut ref_tracking, mode)?;
+        inner = true;
+    }
+    Ok(())
+}
+
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn const_is_valid_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> bool {
+    // Errors, including invalid values, are reported by the regular evaluation.
+    let Ok(alloc) = tcx.eval_to_allocation_raw(key) else {
+        return false;
+    };
+    let cid = key.value;
+    let def = cid.instance.def_id();
+    let ecx = mk_eval_cx(
+        tcx,
+        tcx.def_span(def),
+        key.param_env,
+        CanAccessStatics::from(tcx.is_static(def)),
+    );
+    ecx.raw_const_to_mplace(alloc).and_then(|mplace| validate_const(&ecx, cid, mplace)).is_ok()
+}
+
+#[instrument(skip(tcx), level = "debug")]
+pub fn const_is_target_dependent_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> bool {
+    // Errors are reported by the regular evaluation, so we don't have to worry about them when
+    // evaluating the constant again below.
+    if tcx.eval_to_allocation_raw(key).is_err() {
+        return false;
+    }
+
+    let cid = key.value;
+    let def = cid.instance.def.def_id();
+    let mut ecx = InterpCx::new(
+        tcx,
+        tcx.def_span(def),
+        key.param_env,
+        CompileTimeInterpreter::new(
+            tcx,
+            CanAccessStatics::from(tcx.is_static(def)),
+            CheckAlignment::No,
+        ),
+    );
+    ecx.machine.target_dependent = Some(false);
+    let res = ecx.load_mir(cid.instance.def, cid.promoted);
+    match res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body)) {
+        Ok(_) => ecx.machine.target_dependent == Some(true),
+        Err(_) => false,
+    }
+}
+
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn const_eval_pretty_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> Option<&'tcx str> {
+    let ty = tcx.eval_to_allocation_raw(key).ok()?.ty;
+    // Prefer the printer used for constants in type errors, which only works for valtrees. The
+    // printer for MIR constants also handles values like raw pointers.
+    let pretty = match tcx.eval_to_valtree(key) {
+        Ok(Some(valtree)) => {
+            with_no_trimmed_paths!(ty::Const::new_value(tcx, valtree, ty).to_string())
+        }
+        _ => {
+            let val = tcx.eval_to_const_value_raw(key).ok()?;
+            with_no_trimmed_paths!(mir::ConstantKind::Val(val, ty).to_string())
+        }
+    };
+    Some(std::str::from_utf8(tcx.arena.alloc_slice(pretty.as_bytes())).unwrap())
+}
+
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn const_allocation_align_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> Option<abi::Align> {
+    let const_alloc = tcx.eval_to_allocation_raw(key).ok()?;
+    match tcx.try_get_global_alloc(const_alloc.alloc_id) {
+        Some(GlobalAlloc::Memory(alloc)) => Some(alloc.inner().align),
+        // Evaluation always puts the value in memory, but the layout gives the same answer.
+        _ => Some(tcx.layout_of(key.param_env.and(const_alloc.ty)).ok()?.align.abi),
+    }
+}
+
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn const_used_intrinsics_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> &'tcx FxIndexSet<Symbol> {
+    // Errors are reported by the regular evaluation, so we don't have to worry about them when
+    // evaluating the constant again below.
+    if tcx.eval_to_allocation_raw(key).is_err() {
+        return tcx.arena.alloc(FxIndexSet::default());
+    }
+
+    let cid = key.value;
+    let def = cid.instance.def.def_id();
+    let mut ecx = InterpCx::new(
+        tcx,
+        tcx.def_span(def),
+        key.param_env,
+        CompileTimeInterpreter::new(
+            tcx,
+            CanAccessStatics::from(tcx.is_static(def)),
+            CheckAlignment::No,
+        ),
+    );
+    ecx.machine.used_intrinsics = Some(FxIndexSet::default());
+    let res = ecx.load_mir(cid.instance.def, cid.promoted);
+    let _ = res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body));
+    tcx.arena.alloc(ecx.machine.used_intrinsics.take().unwrap())
+}
+
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn const_referenced_statics_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> &'tcx [DefId] {
+    // Errors are reported by the regular evaluation.
+    let Ok(alloc) = tcx.eval_to_allocation_raw(key) else {
+        return &[];
+    };
+
+    let mut statics = FxIndexSet::default();
+    let mut seen = FxHashSet::default();
+    let mut todo = vec![alloc.alloc_id];
+    while let Some(alloc_id) = todo.pop() {
+        if !seen.insert(alloc_id) {
+            continue;
+        }
+        match tcx.try_get_global_alloc(alloc_id) {
+            Some(GlobalAlloc::Memory(alloc)) => {
+                todo.extend(alloc.inner().provenance().ptrs().values().copied());
+            }
+            // Don't look into the static itself, its address is all the constant refers to.
+            Some(GlobalAlloc::Static(def_id)) => {
+                statics.insert(def_id);
+            }
+            Some(GlobalAlloc::Function(_) | GlobalAlloc::VTable(..)) | None => {}
+        }
+    }
+    tcx.arena.alloc_from_iter(statics)
+}
+
+/// Classifies the constant `key` by its type alone. The constant isn't evaluated, so it may still
+/// fail to evaluate if this returns a shape.
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn const_shape_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> Result<ConstShape, ErrorHandled> {
+    let (param_env, cid) = key.into_parts();
+    let def = cid.instance.def_id();
+    let ty = match cid.promoted {
+        Some(promoted) => tcx.promoted_mir(def)[promoted].return_ty(),
+        None => tcx.type_of(def).subst_identity(),
+    };
+    let ty = cid
+        .instance
+        .try_subst_mir_and_normalize_erasing_regions(tcx, param_env, ty::EarlyBinder::bind(ty))
+        .map_err(|_| ErrorHandled::TooGeneric)?;
+    if let Err(guar) = ty.error_reported() {
+        return Err(ErrorHandled::Reported(guar.into()));
+    }
+    match ty.kind() {
+        ty::Param(_) | ty::Alias(..) | ty::Placeholder(_) | ty::Infer(_) | ty::Bound(..) => {
+            Err(ErrorHandled::TooGeneric)
+        }
+        _ => Ok(ConstShape::from_ty(ty)),
+    }
+}
+
+pub fn const_minimal_int_ty_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> Option<Ty<'tcx>> {
+    let ty = tcx.eval_to_allocation_raw(key).ok()?.ty;
+    let signed = match ty.kind() {
+        ty::Int(_) => true,
+        ty::Uint(_) => false,
+        _ => return None,
+    };
+    let int = tcx.eval_to_const_value_raw(key).ok()?.try_to_scalar_int()?;
+    Some(abi::Integer::from_size(int.minimal_int_size(signed)).ok()?.to_ty(tcx, signed))
+}
+
+/// The result of `eval_recovering_ub`.
+#[derive(Debug)]
+pub struct UbReport<'tcx> {
+    /// UB that evaluation continued after, in the order it happened.
+    pub recovered: Vec<RecoveredUb<'tcx>>,
+    /// The error that stopped evaluation, e.g. UB that the interpreter can't continue after.
+    pub error: Option<InterpError<'tcx>>,
+}
+
+/// Evaluates a constant like `eval_to_allocation_raw`, but continues after UB that the
+/// interpreter knows how to recover from, like wrapping the result of an unchecked operation that
+/// overflowed or ignoring a misaligned access, and collects all of it. Nothing is reported and the
+/// result isn't cached, so this never affects compilation. It is meant for tools that classify
+/// constants, and returns `None` unless `-Z const-eval-recover-ub` is set.
+pub fn eval_recovering_ub<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> Option<UbReport<'tcx>> {
+    if !tcx.sess.opts.unstable_opts.const_eval_recover_ub {
+        return None;
+    }
+
+    let cid = key.value;
+    let def = cid.instance.def.def_id();
+    let mut ecx = InterpCx::new(
+        tcx,
+        tcx.def_span(def),
+        key.param_env,
+        CompileTimeInterpreter::new(
+            tcx,
+            CanAccessStatics::from(tcx.is_static(def)),
+            CheckAlignment::Error,
+        ),
+    );
+    ecx.machine.recovered_ub = Some(Default::default());
+    let res = ecx.load_mir(cid.instance.def, cid.promoted);
+    let error = res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body)).err();
+    Some(UbReport {
+        recovered: ecx.machine.recovered_ub.take().unwrap().into_inner(),
+        error: error.map(|error| error.into_parts().0),
+    })
+}
+
+/// The result of `eval_wrapping_overflow`.
+#[derive(Debug)]
+pub struct WrapOverflowReport<'tcx> {
+    /// The arithmetic overflows that evaluation wrapped around on, in the order they happened.
+    pub overflows: Vec<WrappedOverflow>,
+    /// The value of the constant, or the error that stopped evaluation.
+    pub result: Result<ConstAlloc<'tcx>, InterpError<'tcx>>,
+}
+
+/// Evaluates a constant like `eval_to_allocation_raw`, but continues with the wrapped result
+/// where Rust would panic on arithmetic overflow, and records where that happened. The value is
+/// neither validated nor cached and nothing is reported, so this never affects compilation. It is
+/// meant for tools exploring the behavior of constants, and returns `None` unless
+/// `-Z const-eval-wrap-overflow` is set.
+pub fn eval_wrapping_overflow<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> Option<WrapOverflowReport<'tcx>> {
+    if !tcx.sess.opts.unstable_opts.const_eval_wrap_overflow {
+        return None;
+    }
+
+    let cid = key.value;
+    let def = cid.instance.def.def_id();
+    let mut ecx = InterpCx::new(
+        tcx,
+        tcx.def_span(def),
+        key.param_env,
+        CompileTimeInterpreter::new(
+            tcx,
+            CanAccessStatics::from(tcx.is_static(def)),
+            CheckAlignment::Error,
+        ),
+    );
+    ecx.machine.wrapped_overflows = Some(Vec::new());
+    let res = ecx.load_mir(cid.instance.def, cid.promoted);
+    let result = res
+        .and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body))
+        .map(|mplace| ConstAlloc { alloc_id: mplace.ptr.provenance.unwrap(), ty: mplace.layout.ty })
+        .map_err(|error| error.into_parts().0);
+    Some(WrapOverflowReport { overflows: ecx.machine.wrapped_overflows.take().unwrap(), result })
+}
@@ -0,0 +1,180 @@
+//! Folding of common operations on fully evaluated constants.
+//!
+//! These helpers work directly on `ValTree`s and never spin up an interpreter. They are only
+//! fast paths: every one of them must produce exactly the value the interpreter would produce
+//! when running the corresponding library function, and must bail out with `None` whenever
+//! that cannot be guaranteed.
+
+use rustc_middle::mir::interpret::read_target_uint;
+use rustc_middle::ty::{self, ScalarInt};
+use rustc_target::abi::{Endian, Size};
+use std::num::IntErrorKind;
+
+#[cfg(test)]
+mod tests;
+
+/// Assembles an integer from a byte-array valtree, like `u32::from_le_bytes` or
+/// `i64::from_be_bytes` do.
+///
+/// The size of the resulting integer is the number of bytes in the array. Returns `None` if
+/// `valtree` is not a non-empty branch of at most 16 `u8` leaves.
+pub fn fold_int_from_bytes(valtree: ty::ValTree<'_>, endian: Endian) -> Option<ScalarInt> {
+    let ty::ValTree::Branch(elems) = valtree else {
+        return None;
+    };
+    if elems.is_empty() || elems.len() > std::mem::size_of::<u128>() {
+        return None;
+    }
+    let bytes = elems
+        .iter()
+        .map(|elem| elem.try_to_scalar_int()?.try_to_u8().ok())
+        .collect::<Option<Vec<u8>>>()?;
+    // This is the same routine the interpreter uses to read integers out of memory.
+    let bits = read_target_uint(endian, &bytes).ok()?;
+    ScalarInt::try_from_uint(bits, Size::from_bytes(bytes.len()))
+}
+
+/// Parses the bytes of a `&str` valtree as an integer in base `radix`, like
+/// `u32::from_str_radix` or `i64::from_str_radix` do, including their choice of error for
+/// malformed input.
+///
+/// The integer has the given `size` and signedness. Returns `None` if `valtree` is not a branch
+/// of `u8` leaves, or if `from_str_radix` would panic because `radix` is not in `2..=36`.
+pub fn fold_int_from_str_radix(
+    valtree: ty::ValTree<'_>,
+    radix: u32,
+    size: Size,
+    signed: bool,
+) -> Option<Result<ScalarInt, IntErrorKind>> {
+    if !(2..=36).contains(&radix) || size.bytes() == 0 || size.bits() > 128 {
+        return None;
+    }
+    let ty::ValTree::Branch(elems) = valtree else {
+        return None;
+    };
+    let bytes = elems
+        .iter()
+        .map(|elem| elem.try_to_scalar_int()?.try_to_u8().ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    let (negative, digits) = match &bytes[..] {
+        [] => return Some(Err(IntErrorKind::Empty)),
+        [b'+' | b'-'] => return Some(Err(IntErrorKind::InvalidDigit)),
+        [b'+', rest @ ..] => (false, rest),
+        // Unsigned types treat a leading `-` as an invalid digit.
+        [b'-', rest @ ..] if signed => (true, rest),
+        _ => (false, &bytes[..]),
+    };
+    let max_magnitude = match (signed, negative) {
+        (false, _) => size.unsigned_int_max(),
+        (true, false) => size.signed_int_max() as u128,
+        (true, true) => size.signed_int_max() as u128 + 1,
+    };
+    let overflow = if negative { IntErrorKind::NegOverflow } else { IntErrorKind::PosOverflow };
+    // Like the library, report whichever of an invalid digit or an overflow comes first.
+    let mut magnitude: u128 = 0;
+    for &byte in digits {
+        let Some(digit) = char::from(byte).to_digit(radix) else {
+            return Some(Err(IntErrorKind::InvalidDigit));
+        };
+        magnitude = match magnitude
+            .checked_mul(radix.into())
+            .and_then(|m| m.checked_add(digit.into()))
+            .filter(|&m| m <= max_magnitude)
+        {
+            Some(m) => m,
+            None => return Some(Err(overflow)),
+        };
+    }
+    let bits = if negative { size.truncate(magnitude.wrapping_neg()) } else { magnitude };
+    Some(Ok(ScalarInt::try_from_uint(bits, size).unwrap()))
+}
+
+/// A classification method of `char`, see `fold_char_class`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharClass {
+    /// `char::is_ascii`
+    Ascii,
+    /// `char::is_alphabetic`
+    Alphabetic,
+    /// `char::is_alphanumeric`
+    Alphanumeric,
+    /// `char::is_numeric`
+    Numeric,
+    /// `char::is_lowercase`
+    Lowercase,
+    /// `char::is_uppercase`
+    Uppercase,
+    /// `char::is_whitespace`
+    Whitespace,
+    /// `char::is_ascii_alphabetic`
+    AsciiAlphabetic,
+    /// `char::is_ascii_alphanumeric`
+    AsciiAlphanumeric,
+    /// `char::is_ascii_digit`
+    AsciiDigit,
+    /// `char::is_ascii_hexdigit`
+    AsciiHexdigit,
+    /// `char::is_ascii_lowercase`
+    AsciiLowercase,
+    /// `char::is_ascii_uppercase`
+    AsciiUppercase,
+    /// `char::is_ascii_punctuation`
+    AsciiPunctuation,
+    /// `char::is_ascii_graphic`
+    AsciiGraphic,
+    /// `char::is_ascii_whitespace`
+    AsciiWhitespace,
+    /// `char::is_ascii_control`
+    AsciiControl,
+}
+
+impl CharClass {
+    /// Whether classifying a non-ASCII `char` requires the Unicode tables of the library.
+    fn uses_unicode_tables(self) -> bool {
+        matches!(
+            self,
+            CharClass::Alphabetic
+                | CharClass::Alphanumeric
+                | CharClass::Numeric
+                | CharClass::Lowercase
+                | CharClass::Uppercase
+                | CharClass::Whitespace
+        )
+    }
+}
+
+/// Classifies a `char` valtree like the `char` method corresponding to `class`, e.g.
+/// `char::is_alphabetic`.
+///
+/// The `is_ascii*` classifications are answered for every `char`. The other ones are only
+/// answered for ASCII `char`s: for all others they depend on the Unicode version of the library
+/// being compiled against, so `None` is returned and the interpreter has to run the library
+/// function. Also returns `None` if `valtree` is not a valid `char` leaf.
+pub fn fold_char_class(valtree: ty::ValTree<'_>, class: CharClass) -> Option<bool> {
+    let c = char::try_from(valtree.try_to_scalar_int()?).ok()?;
+    if !c.is_ascii() && class.uses_unicode_tables() {
+        return None;
+    }
+    // The classification of ASCII characters is the same in every Unicode version, and the
+    // `is_ascii*` methods are plain arithmetic, so the host's library gives the right answer.
+    Some(match class {
+        CharClass::Ascii => c.is_ascii(),
+        CharClass::Alphabetic => c.is_alphabetic(),
+        CharClass::Alphanumeric => c.is_alphanumeric(),
+        CharClass::Numeric => c.is_numeric(),
+        CharClass::Lowercase => c.is_lowercase(),
+        CharClass::Uppercase => c.is_uppercase(),
+        CharClass::Whitespace => c.is_whitespace(),
+        CharClass::AsciiAlphabetic => c.is_ascii_alphabetic(),
+        CharClass::AsciiAlphanumeric => c.is_ascii_alphanumeric(),
+        CharClass::AsciiDigit => c.is_ascii_digit(),
+        CharClass::AsciiHexdigit => c.is_ascii_hexdigit(),
+        CharClass::AsciiLowercase => c.is_ascii_lowercase(),
+        CharClass::AsciiUppercase => c.is_ascii_uppercase(),
+        CharClass::AsciiPunctuation => c.is_ascii_punctuation(),
+        CharClass::AsciiGraphic => c.is_ascii_graphic(),
+        CharClass::AsciiWhitespace => c.is_ascii_whitespace(),
+        CharClass::AsciiControl => c.is_ascii_control(),
+    })
+}
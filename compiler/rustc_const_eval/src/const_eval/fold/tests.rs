@@ -0,0 +1,201 @@
+use super::*;
+
+fn byte_leaves(bytes: &[u8]) -> Vec<ty::ValTree<'static>> {
+    bytes.iter().map(|&b| ty::ValTree::Leaf(ScalarInt::from(b))).collect()
+}
+
+fn fold_bits(bytes: &[u8], endian: Endian) -> Option<u128> {
+    let leaves = byte_leaves(bytes);
+    let int = fold_int_from_bytes(ty::ValTree::Branch(&leaves), endian)?;
+    Some(int.assert_bits(Size::from_bytes(bytes.len())))
+}
+
+#[test]
+fn int_from_bytes_matches_std() {
+    let bytes = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+
+    assert_eq!(fold_bits(&bytes[..1], Endian::Little), Some(u8::from_le_bytes([0x12]).into()));
+    assert_eq!(
+        fold_bits(&bytes[..2], Endian::Little),
+        Some(u16::from_le_bytes([0x12, 0x34]).into())
+    );
+    assert_eq!(fold_bits(&bytes[..2], Endian::Big), Some(u16::from_be_bytes([0x12, 0x34]).into()));
+    assert_eq!(
+        fold_bits(&bytes[..4], Endian::Little),
+        Some(u32::from_le_bytes([0x12, 0x34, 0x56, 0x78]).into())
+    );
+    assert_eq!(
+        fold_bits(&bytes[..4], Endian::Big),
+        Some(u32::from_be_bytes([0x12, 0x34, 0x56, 0x78]).into())
+    );
+    assert_eq!(fold_bits(&bytes, Endian::Little), Some(u64::from_le_bytes(bytes).into()));
+    assert_eq!(fold_bits(&bytes, Endian::Big), Some(u64::from_be_bytes(bytes).into()));
+
+    let wide: [u8; 16] = std::array::from_fn(|i| (i * 17) as u8);
+    assert_eq!(fold_bits(&wide, Endian::Little), Some(u128::from_le_bytes(wide)));
+    assert_eq!(fold_bits(&wide, Endian::Big), Some(u128::from_be_bytes(wide)));
+}
+
+#[test]
+fn int_from_bytes_signed() {
+    // Signed integers have the same bit pattern, the sign is only a matter of interpretation.
+    let bits = fold_bits(&[0xff, 0x7f], Endian::Big).unwrap();
+    assert_eq!(bits as u16 as i16, i16::from_be_bytes([0xff, 0x7f]));
+    let bits = fold_bits(&[0x00, 0x80], Endian::Little).unwrap();
+    assert_eq!(bits as u16 as i16, i16::MIN);
+}
+
+#[test]
+fn int_from_bytes_rejects_non_bytes() {
+    assert_eq!(fold_bits(&[], Endian::Little), None);
+    assert_eq!(fold_bits(&[0; 17], Endian::Little), None);
+
+    let leaves =
+        [ty::ValTree::Leaf(ScalarInt::from(1u8)), ty::ValTree::Leaf(ScalarInt::from(1u16))];
+    assert_eq!(fold_int_from_bytes(ty::ValTree::Branch(&leaves), Endian::Little), None);
+    assert_eq!(fold_int_from_bytes(ty::ValTree::Leaf(ScalarInt::from(1u32)), Endian::Big), None);
+}
+
+fn fold_str<T>(s: &str, radix: u32, signed: bool) -> Option<Result<u128, IntErrorKind>> {
+    let leaves = byte_leaves(s.as_bytes());
+    let size = Size::from_bytes(std::mem::size_of::<T>());
+    let int = fold_int_from_str_radix(ty::ValTree::Branch(&leaves), radix, size, signed)?;
+    Some(int.map(|int| int.assert_bits(size)))
+}
+
+/// Checks that folding agrees with the library implementation, which is what the interpreter
+/// runs, on the value or the kind of error.
+macro_rules! assert_from_str_radix_matches_std {
+    ($ty:ty, $signed:expr, $s:expr, $radix:expr) => {{
+        let expected = <$ty>::from_str_radix($s, $radix)
+            .map(|v| Size::from_bytes(std::mem::size_of::<$ty>()).truncate(v as u128))
+            .map_err(|e| e.kind().clone());
+        assert_eq!(
+            fold_str::<$ty>($s, $radix, $signed),
+            Some(expected),
+            "{}::from_str_radix({:?}, {})",
+            stringify!($ty),
+            $s,
+            $radix,
+        );
+    }};
+}
+
+#[test]
+fn int_from_str_radix_matches_std() {
+    let inputs = [
+        "",
+        "+",
+        "-",
+        "0",
+        "+0",
+        "-0",
+        "1",
+        "-1",
+        "+-1",
+        "--1",
+        "7f",
+        "7F",
+        "80",
+        "-80",
+        "-81",
+        "ff",
+        "100",
+        "255",
+        "256",
+        "-128",
+        "-129",
+        "127",
+        "128",
+        "z",
+        "Z",
+        "10z",
+        "1_000",
+        " 1",
+        "1 ",
+        "4294967295",
+        "4294967296",
+        "-2147483648",
+        "-2147483649",
+        "zzzzzzzzzzzzzzzzzzzzzzzzzzzz",
+        "340282366920938463463374607431768211455",
+        "340282366920938463463374607431768211456",
+        "-170141183460469231731687303715884105728",
+        "-170141183460469231731687303715884105729",
+        "170141183460469231731687303715884105727",
+        "170141183460469231731687303715884105728",
+        "999999999999999999999999999999999999999999x",
+        "\u{e9}",
+    ];
+    for s in inputs {
+        for radix in [2, 8, 10, 16, 36] {
+            assert_from_str_radix_matches_std!(u8, false, s, radix);
+            assert_from_str_radix_matches_std!(i8, true, s, radix);
+            assert_from_str_radix_matches_std!(u32, false, s, radix);
+            assert_from_str_radix_matches_std!(i32, true, s, radix);
+            assert_from_str_radix_matches_std!(u128, false, s, radix);
+            assert_from_str_radix_matches_std!(i128, true, s, radix);
+        }
+    }
+}
+
+#[test]
+fn int_from_str_radix_bails_out() {
+    // `from_str_radix` panics for these radixes, which folding must leave to the interpreter.
+    assert_eq!(fold_str::<u32>("1", 1, false), None);
+    assert_eq!(fold_str::<u32>("1", 37, false), None);
+
+    let leaves =
+        [ty::ValTree::Leaf(ScalarInt::from(b'1')), ty::ValTree::Leaf(ScalarInt::from('1'))];
+    let size = Size::from_bytes(4);
+    assert_eq!(fold_int_from_str_radix(ty::ValTree::Branch(&leaves), 10, size, false), None);
+    assert_eq!(
+        fold_int_from_str_radix(ty::ValTree::Leaf(ScalarInt::from(1u8)), 10, size, false),
+        None
+    );
+}
+
+#[test]
+fn char_class_matches_std() {
+    let classes: [(CharClass, fn(&char) -> bool); 17] = [
+        (CharClass::Ascii, |c| c.is_ascii()),
+        (CharClass::Alphabetic, |c| c.is_alphabetic()),
+        (CharClass::Alphanumeric, |c| c.is_alphanumeric()),
+        (CharClass::Numeric, |c| c.is_numeric()),
+        (CharClass::Lowercase, |c| c.is_lowercase()),
+        (CharClass::Uppercase, |c| c.is_uppercase()),
+        (CharClass::Whitespace, |c| c.is_whitespace()),
+        (CharClass::AsciiAlphabetic, char::is_ascii_alphabetic),
+        (CharClass::AsciiAlphanumeric, char::is_ascii_alphanumeric),
+        (CharClass::AsciiDigit, char::is_ascii_digit),
+        (CharClass::AsciiHexdigit, char::is_ascii_hexdigit),
+        (CharClass::AsciiLowercase, char::is_ascii_lowercase),
+        (CharClass::AsciiUppercase, char::is_ascii_uppercase),
+        (CharClass::AsciiPunctuation, char::is_ascii_punctuation),
+        (CharClass::AsciiGraphic, char::is_ascii_graphic),
+        (CharClass::AsciiWhitespace, char::is_ascii_whitespace),
+        (CharClass::AsciiControl, char::is_ascii_control),
+    ];
+    let non_ascii = ['\u{80}', '\u{a0}', 'é', 'ß', 'Σ', '٣', '\u{2003}', '中', '🦀', char::MAX];
+    for c in ('\0'..='\x7f').chain(non_ascii) {
+        for (class, std) in classes {
+            let folded = fold_char_class(ty::ValTree::Leaf(ScalarInt::from(c)), class);
+            if c.is_ascii() || !class.uses_unicode_tables() {
+                assert_eq!(folded, Some(std(&c)), "{c:?} {class:?}");
+            } else {
+                // Left to the interpreter, as the answer depends on the Unicode tables.
+                assert_eq!(folded, None, "{c:?} {class:?}");
+            }
+        }
+    }
+
+    // Vertical tab is whitespace, but not ASCII whitespace.
+    let vt = ty::ValTree::Leaf(ScalarInt::from('\x0b'));
+    assert_eq!(fold_char_class(vt, CharClass::Whitespace), Some(true));
+    assert_eq!(fold_char_class(vt, CharClass::AsciiWhitespace), Some(false));
+
+    // Not a `char`.
+    let surrogate = ty::ValTree::Leaf(ScalarInt::from(0xd800u32));
+    assert_eq!(fold_char_class(surrogate, CharClass::Ascii), None);
+    assert_eq!(fold_char_class(ty::ValTree::zst(), CharClass::Ascii), None);
+}
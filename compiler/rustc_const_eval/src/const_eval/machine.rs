@@ -1,29 +1,31 @@
+use either::Left;
 use rustc_hir::def::DefKind;
 use rustc_hir::{LangItem, CRATE_HIR_ID};
 use rustc_middle::mir;
 use rustc_middle::mir::interpret::PointerArithmetic;
 use rustc_middle::ty::layout::{FnAbiOf, TyAndLayout};
-use rustc_middle::ty::{self, Ty, TyCtxt};
-use rustc_session::lint::builtin::INVALID_ALIGNMENT;
+use rustc_middle::ty::{self, ConstInt, Ty, TyCtxt};
 use std::borrow::Borrow;
+use std::cell::RefCell;
 use std::hash::Hash;
 use std::ops::ControlFlow;
 
-use rustc_data_structures::fx::FxIndexMap;
 use rustc_data_structures::fx::IndexEntry;
+use rustc_data_structures::fx::{FxIndexMap, FxIndexSet};
 use std::fmt;
 
 use rustc_ast::Mutability;
 use rustc_hir::def_id::DefId;
-use rustc_middle::mir::AssertMessage;
+use rustc_middle::mir::{AssertKind, AssertMessage};
 use rustc_span::symbol::{sym, Symbol};
+use rustc_span::Span;
 use rustc_target::abi::{Align, Size};
 use rustc_target::spec::abi::Abi as CallAbi;
 
 use crate::errors::{LongRunning, LongRunningWarn};
 use crate::interpret::{
-    self, compile_time_machine, AllocId, ConstAllocation, FnVal, Frame, ImmTy, InterpCx,
-    InterpResult, OpTy, PlaceTy, Pointer, Scalar,
+    self, compile_time_machine, AllocId, AllocRange, ConstAllocation, FnVal, Frame, ImmTy,
+    InterpCx, InterpError, InterpErrorInfo, InterpResult, OpTy, PlaceTy, Pointer, Scalar,
 };
 use crate::{errors, fluent_generated as fluent};
 
@@ -48,6 +50,13 @@ pub struct CompileTimeInterpreter<'mir, 'tcx> {
     /// Set to `usize::MAX` to never report anything.
     pub(super) num_evaluated_steps: usize,
 
+    /// The number of steps taken since `num_evaluated_steps` was last updated.
+    pending_steps: usize,
+
+    /// How many steps to batch up before updating `num_evaluated_steps` and checking it against
+    /// the step limit. Set from `-Z const-eval-step-granularity`.
+    step_granularity: usize,
+
     /// The virtual call stack.
     pub(super) stack: Vec<Frame<'mir, 'tcx, AllocId, ()>>,
 
@@ -61,6 +70,56 @@ pub struct CompileTimeInterpreter<'mir, 'tcx> {
 
     /// Whether to check alignment during evaluation.
     pub(super) check_alignment: CheckAlignment,
+
+    /// The number of bytes currently allocated in machine memory.
+    allocated_bytes: u64,
+
+    /// The maximum value `allocated_bytes` may reach. Set from `-Z const-eval-max-memory`.
+    memory_limit: Option<u64>,
+
+    /// Whether the evaluation did something whose result may differ between targets, like
+    /// computing with `usize` values or reinterpreting bytes. `None` if this isn't tracked, which
+    /// is the case unless we are computing the `const_is_target_dependent` query.
+    pub(super) target_dependent: Option<bool>,
+
+    /// The names of the intrinsics called so far, in the order of their first call. `None` if
+    /// this isn't tracked, which is the case unless we are computing the `const_used_intrinsics`
+    /// query.
+    pub(super) used_intrinsics: Option<FxIndexSet<Symbol>>,
+
+    /// Whether to print every statement and terminator that is executed. Set for the constant
+    /// named by `-Z trace-const-eval`.
+    pub(super) trace: bool,
+
+    /// UB that evaluation continued after. `None` unless we are evaluating with
+    /// `eval_recovering_ub`, otherwise all UB is an error.
+    pub(super) recovered_ub: Option<RefCell<Vec<RecoveredUb<'tcx>>>>,
+
+    /// Misaligned accesses found with `CheckAlignment::FutureIncompat`, which are reported after
+    /// the evaluation.
+    pub(super) alignment_lints: PendingAlignmentLints,
+
+    /// Arithmetic overflows that evaluation wrapped around on instead of panicking. `None` unless
+    /// we are evaluating with `eval_wrapping_overflow`.
+    pub(super) wrapped_overflows: Option<Vec<WrappedOverflow>>,
+
+    /// The caller location of the root frame, if evaluation was started directly on a function,
+    /// which may be `#[track_caller]`. `None` when evaluating the body of a global.
+    pub(super) root_caller_location: Option<Span>,
+}
+
+/// UB that was encountered by `eval_recovering_ub`, but that evaluation continued after.
+#[derive(Debug)]
+pub struct RecoveredUb<'tcx> {
+    pub span: Span,
+    pub error: InterpError<'tcx>,
+}
+
+/// An arithmetic overflow that `eval_wrapping_overflow` continued after with the wrapped result.
+#[derive(Debug)]
+pub struct WrappedOverflow {
+    pub span: Span,
+    pub kind: AssertKind<ConstInt>,
 }
 
 #[derive(Copy, Clone)]
@@ -97,16 +156,87 @@ fn from(value: bool) -> Self {
 
 impl<'mir, 'tcx> CompileTimeInterpreter<'mir, 'tcx> {
     pub(crate) fn new(
+        tcx: TyCtxt<'tcx>,
         can_access_statics: CanAccessStatics,
         check_alignment: CheckAlignment,
     ) -> Self {
         CompileTimeInterpreter {
             num_evaluated_steps: 0,
+            pending_steps: 0,
+            step_granularity: tcx.sess.opts.unstable_opts.const_eval_step_granularity.max(1),
             stack: Vec::new(),
             can_access_statics,
             check_alignment,
+            allocated_bytes: 0,
+            memory_limit: tcx.sess.opts.unstable_opts.const_eval_max_memory,
+            target_dependent: None,
+            used_intrinsics: None,
+            trace: false,
+            recovered_ub: None,
+            alignment_lints: PendingAlignmentLints::default(),
+            wrapped_overflows: None,
+            root_caller_location: None,
         }
     }
+
+    /// Checks that allocating another `size` bytes during the evaluation of `item` stays within
+    /// `-Z const-eval-max-memory`.
+    pub(super) fn check_memory_limit(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        item: DefId,
+        size: Size,
+    ) -> InterpResult<'tcx> {
+        if let Some(limit) = self.memory_limit
+            && self.allocated_bytes.saturating_add(size.bytes()) > limit
+        {
+            let item = tcx.def_path_str(item);
+            throw_machine_stop!(ConstEvalErrKind::MemoryLimitExceeded { item, limit });
+        }
+        Ok(())
+    }
+
+    /// Records that the evaluation is target dependent if `f` says so. `f` is only called while
+    /// target dependence is being tracked and hasn't been detected yet.
+    fn note_target_dependent(&mut self, f: impl FnOnce() -> bool) {
+        if self.target_dependent == Some(false) && f() {
+            self.target_dependent = Some(true);
+        }
+    }
+}
+
+/// Checks whether a function body computes with values whose size or byte order depend on the
+/// target. This is a heuristic: it looks at the types of all locals and at the operations that
+/// reinterpret bytes, not at what the body actually does with them.
+fn body_is_target_dependent<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    instance: ty::Instance<'tcx>,
+    body: &mir::Body<'tcx>,
+) -> bool {
+    let ptr_sized = |ty: Ty<'tcx>| {
+        let ty = instance
+            .try_subst_mir_and_normalize_erasing_regions(tcx, param_env, ty::EarlyBinder::bind(ty))
+            .unwrap_or(ty);
+        ty.walk().any(|arg| {
+            arg.as_type().is_some_and(|ty| {
+                ty.is_ptr_sized_integral() || matches!(ty.kind(), ty::RawPtr(_) | ty::FnPtr(_))
+            })
+        })
+    };
+    if body.local_decls.iter().any(|decl| ptr_sized(decl.ty)) {
+        return true;
+    }
+    body.basic_blocks.iter().flat_map(|bb| &bb.statements).any(|stmt| {
+        matches!(
+            stmt.kind,
+            mir::StatementKind::Assign(box (
+                _,
+                mir::Rvalue::Cast(mir::CastKind::Transmute, ..)
+                | mir::Rvalue::NullaryOp(mir::NullOp::SizeOf | mir::NullOp::AlignOf, _),
+            ))
+        )
+    })
 }
 
 impl<K: Hash + Eq, V> interpret::AllocMap<K, V> for FxIndexMap<K, V> {
@@ -373,7 +503,7 @@ fn alignment_check_failed(
     ) -> InterpResult<'tcx, ()> {
         let err = err_ub!(AlignmentCheckFailed { has, required }).into();
         match check {
-            CheckAlignment::Error => Err(err),
+            CheckAlignment::Error => Self::recoverable_ub(ecx, err),
             CheckAlignment::No => span_bug!(
                 ecx.cur_span(),
                 "`alignment_check_failed` called when no alignment check requested"
@@ -383,14 +513,14 @@ fn alignment_check_failed(
                 backtrace.print_backtrace();
                 let (span, frames) = super::get_span_and_frames(&ecx);
 
-                ecx.tcx.emit_spanned_lint(
-                    INVALID_ALIGNMENT,
-                    ecx.stack().iter().find_map(|frame| frame.lint_root()).unwrap_or(CRATE_HIR_ID),
+                ecx.machine.alignment_lints.add(
                     span,
+                    ecx.stack().iter().find_map(|frame| frame.lint_root()).unwrap_or(CRATE_HIR_ID),
                     errors::AlignmentCheckFailed {
                         has: has.bytes(),
                         required: required.bytes(),
                         frames,
+                        repeated: None,
                     },
                 );
                 Ok(())
@@ -398,6 +528,18 @@ fn alignment_check_failed(
         }
     }
 
+    fn recoverable_ub(
+        ecx: &InterpCx<'mir, 'tcx, Self>,
+        err: InterpErrorInfo<'tcx>,
+    ) -> InterpResult<'tcx> {
+        let Some(recovered_ub) = &ecx.machine.recovered_ub else {
+            return Err(err);
+        };
+        let (error, _) = err.into_parts();
+        recovered_ub.borrow_mut().push(RecoveredUb { span: ecx.cur_span(), error });
+        Ok(())
+    }
+
     fn load_mir(
         ecx: &InterpCx<'mir, 'tcx, Self>,
         instance: ty::InstanceDef<'tcx>,
@@ -481,6 +623,16 @@ fn call_intrinsic(
         target: Option<mir::BasicBlock>,
         _unwind: mir::UnwindAction,
     ) -> InterpResult<'tcx> {
+        ecx.machine.note_target_dependent(|| {
+            matches!(
+                ecx.tcx.item_name(instance.def_id()),
+                sym::transmute | sym::size_of | sym::min_align_of | sym::pref_align_of
+            )
+        });
+        if let Some(used_intrinsics) = &mut ecx.machine.used_intrinsics {
+            used_intrinsics.insert(ecx.tcx.item_name(instance.def_id()));
+        }
+
         // Shared intrinsics.
         if ecx.emulate_intrinsic(instance, args, dest, target)? {
             return Ok(());
@@ -590,6 +742,26 @@ fn assert_panic(
                 }
             }
         };
+        if msg.is_optional_overflow_check() && ecx.machine.wrapped_overflows.is_some() {
+            // The checked operation already computed the wrapped result, so we can just continue
+            // at the target of the assertion.
+            let span = ecx.cur_span();
+            ecx.machine
+                .wrapped_overflows
+                .as_mut()
+                .unwrap()
+                .push(WrappedOverflow { span, kind: err });
+            let Left(loc) = ecx.frame().loc else {
+                span_bug!(span, "assertion failed outside of a basic block")
+            };
+            let mir::TerminatorKind::Assert { target, .. } =
+                ecx.frame().body.basic_blocks[loc.block].terminator().kind
+            else {
+                span_bug!(span, "`assert_panic` called for a terminator that isn't an assertion")
+            };
+            ecx.go_to_block(target);
+            return Ok(());
+        }
         Err(ConstEvalErrKind::AssertFailure(err).into())
     }
 
@@ -606,10 +778,43 @@ fn binary_ptr_op(
         throw_unsup_format!("pointer arithmetic or comparison is not supported at compile-time");
     }
 
+    fn after_statement(
+        ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        stmt: &mir::Statement<'tcx>,
+    ) -> InterpResult<'tcx> {
+        if ecx.machine.trace {
+            eprintln!("{}: {stmt:?}", ecx.frame().instance);
+            if let mir::StatementKind::Assign(box (place, _)) = &stmt.kind {
+                let local = interpret::Place::Local { frame: ecx.frame_idx(), local: place.local };
+                eprintln!("    {:?}", ecx.dump_place(local));
+            }
+        }
+        Ok(())
+    }
+
+    fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
+        if ecx.machine.trace
+            && let Some(loc) = ecx.frame().loc.left()
+        {
+            let terminator = ecx.frame().body.basic_blocks[loc.block].terminator();
+            eprintln!("{}: {:?}", ecx.frame().instance, terminator.kind);
+        }
+        Ok(())
+    }
+
     fn increment_const_eval_counter(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
+        // Steps are only accounted for in batches of `step_granularity`, the checks below are
+        // written so that they do not depend on hitting any value exactly.
+        ecx.machine.pending_steps += 1;
+        if ecx.machine.pending_steps < ecx.machine.step_granularity {
+            return Ok(());
+        }
+        let steps = std::mem::take(&mut ecx.machine.pending_steps);
+
         // The step limit has already been hit in a previous call to `increment_const_eval_counter`.
 
-        if let Some(new_steps) = ecx.machine.num_evaluated_steps.checked_add(1) {
+        let old_steps = ecx.machine.num_evaluated_steps;
+        if let Some(new_steps) = old_steps.checked_add(steps) {
             let (limit, start) = if ecx.tcx.sess.opts.unstable_opts.tiny_const_eval_limit {
                 (TINY_LINT_TERMINATOR_LIMIT, TINY_LINT_TERMINATOR_LIMIT)
             } else {
@@ -621,7 +826,7 @@ fn increment_const_eval_counter(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpR
             // to ensure `loop {}` doesn't just go forever.
             // In case that lint got reduced, in particular for `--cap-lint` situations, we also
             // have a hard warning shown every now and then for really long executions.
-            if new_steps == limit {
+            if old_steps < limit && new_steps >= limit {
                 // By default, we stop after a million steps, but the user can disable this lint
                 // to be able to run until the heat death of the universe or power loss, whichever
                 // comes first.
@@ -649,10 +854,12 @@ fn increment_const_eval_counter(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpR
                         .delay_span_bug(span, "The deny lint should have already errored");
                     throw_inval!(AlreadyReported(guard.into()));
                 }
-            } else if new_steps > start && new_steps.is_power_of_two() {
+            } else if new_steps > start
+                && (old_steps + 1).checked_next_power_of_two().is_some_and(|p| p <= new_steps)
+            {
                 // Only report after a certain number of terminators have been evaluated and the
-                // current number of evaluated terminators is a power of 2. The latter gives us a cheap
-                // way to implement exponential backoff.
+                // current number of evaluated terminators passed a power of 2. The latter gives us
+                // a cheap way to implement exponential backoff.
                 let span = ecx.cur_span();
                 ecx.tcx.sess.emit_warning(LongRunningWarn { span, item_span: ecx.tcx.span });
             }
@@ -675,6 +882,10 @@ fn init_frame_extra(
         ecx: &mut InterpCx<'mir, 'tcx, Self>,
         frame: Frame<'mir, 'tcx>,
     ) -> InterpResult<'tcx, Frame<'mir, 'tcx>> {
+        let (tcx, param_env) = (*ecx.tcx, ecx.param_env);
+        ecx.machine.note_target_dependent(|| {
+            body_is_target_dependent(tcx, param_env, frame.instance, frame.body)
+        });
         // Enforce stack size limit. Add 1 because this is run before the new frame is pushed.
         if !ecx.recursion_limit.value_within_limit(ecx.stack().len() + 1) {
             throw_exhaust!(StackFrameLimitReached)
@@ -697,6 +908,37 @@ fn stack_mut<'a>(
         &mut ecx.machine.stack
     }
 
+    #[inline(always)]
+    fn root_caller_location(ecx: &InterpCx<'mir, 'tcx, Self>) -> Option<Span> {
+        ecx.machine.root_caller_location
+    }
+
+    fn before_memory_allocation(
+        ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        size: Size,
+        _kind: interpret::MemoryKind<Self::MemoryKind>,
+    ) -> InterpResult<'tcx> {
+        // Allocations done outside of any frame are just the compiler moving around values
+        // that have already been evaluated. The one exception is the return place of the
+        // evaluated item, which `eval_body_using_ecx` checks before allocating it.
+        if let Some(root) = ecx.machine.stack.first() {
+            ecx.machine.check_memory_limit(*ecx.tcx, root.instance.def_id(), size)?;
+        }
+        ecx.machine.allocated_bytes = ecx.machine.allocated_bytes.saturating_add(size.bytes());
+        Ok(())
+    }
+
+    fn before_memory_deallocation(
+        _tcx: TyCtxt<'tcx>,
+        machine: &mut Self,
+        _alloc_extra: &mut Self::AllocExtra,
+        _prov: (AllocId, Self::ProvenanceExtra),
+        range: AllocRange,
+    ) -> InterpResult<'tcx> {
+        machine.allocated_bytes = machine.allocated_bytes.saturating_sub(range.size.bytes());
+        Ok(())
+    }
+
     fn before_access_global(
         _tcx: TyCtxt<'tcx>,
         machine: &Self,
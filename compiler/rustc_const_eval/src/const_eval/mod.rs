@@ -1,25 +1,39 @@
 // Not in interpret to make sure we do not use private implementation details
 
-use crate::errors::MaxNumNodesInConstErr;
+use crate::errors::{MaxNumNodesInConstErr, NonCanonicalConstGeneric};
 use crate::interpret::{
-    intern_const_alloc_recursive, ConstValue, InternKind, InterpCx, InterpResult, Scalar,
+    self, intern_const_alloc_recursive, ConstValue, ImmTy, InternKind, InterpCx, InterpResult,
+    MPlaceTy, OpTy, Scalar, StackPopCleanup,
 };
+use either::{Left, Right};
+use rustc_ast as ast;
+use rustc_hir as hir;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
 use rustc_middle::mir;
-use rustc_middle::mir::interpret::{EvalToValTreeResult, GlobalId};
-use rustc_middle::ty::{self, TyCtxt};
-use rustc_span::{source_map::DUMMY_SP, symbol::Symbol};
+use rustc_middle::mir::interpret::{ConstAlloc, ConstAllocation, EvalToValTreeResult, GlobalId};
+use rustc_middle::ty::layout::{LayoutCx, LayoutOf, PrimitiveExt};
+use rustc_middle::ty::{self, SubstsRef, Ty, TyCtxt, TypeVisitableExt};
+use rustc_session::lint::builtin::NON_CANONICAL_CONST_GENERIC;
+use rustc_span::{source_map::DUMMY_SP, symbol::Symbol, Span};
+use rustc_target::abi::{FieldsShape, Size, TagEncoding, VariantIdx, Variants};
+use std::iter;
+use std::ops::RangeInclusive;
 
 mod error;
 mod eval_queries;
 mod fn_queries;
+mod fold;
 mod machine;
+pub mod testing;
 mod valtrees;
 
 pub use error::*;
 pub use eval_queries::*;
 pub use fn_queries::*;
+pub use fold::*;
 pub use machine::*;
-pub(crate) use valtrees::{const_to_valtree_inner, valtree_to_const_value};
+pub(crate) use valtrees::{const_to_valtree_inner, const_val_to_valtree, valtree_to_const_value};
 
 pub(crate) fn const_caller_location(
     tcx: TyCtxt<'_>,
@@ -35,6 +49,97 @@ pub(crate) fn const_caller_location(
     ConstValue::Scalar(Scalar::from_maybe_pointer(loc_place.ptr, &tcx))
 }
 
+/// Only const generic arguments whose value has at most this magnitude are reported by the
+/// `non_canonical_const_generic` lint, larger values are often clearer as a computation.
+const NON_CANONICAL_CONST_GENERIC_MAX: u128 = 999;
+
+/// Fires the `non_canonical_const_generic` lint if `cid` is a const generic argument that only
+/// does arithmetic on integer literals, like `{ 2 + 2 }`, and evaluated to the small integer
+/// `int`.
+fn lint_non_canonical_const_generic<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    cid: GlobalId<'tcx>,
+    int: ty::ScalarInt,
+    signed: bool,
+) {
+    let Some(def) = cid.instance.def_id().as_local() else {
+        return;
+    };
+    if cid.promoted.is_some() || tcx.def_kind(def) != DefKind::AnonConst {
+        return;
+    }
+    let hir_id = tcx.hir().local_def_id_to_hir_id(def);
+    let is_generic_arg = !matches!(
+        tcx.hir().get_parent(hir_id),
+        hir::Node::Ty(hir::Ty { kind: hir::TyKind::Array(..) | hir::TyKind::Typeof(_), .. })
+            | hir::Node::Expr(hir::Expr {
+                kind: hir::ExprKind::Repeat(..) | hir::ExprKind::InlineAsm(_),
+                ..
+            })
+            | hir::Node::Item(hir::Item { kind: hir::ItemKind::GlobalAsm(_), .. })
+            | hir::Node::Variant(_)
+            | hir::Node::GenericParam(_)
+    );
+    if !is_generic_arg {
+        return;
+    }
+    let value = tcx.hir().body(tcx.hir().body_owned_by(def)).value;
+    if !is_literal_arithmetic(value) || is_literal(value) {
+        return;
+    }
+    let bits = int.assert_bits(int.size());
+    let (negative, magnitude) = if signed {
+        let value = int.size().sign_extend(bits) as i128;
+        (value < 0, value.unsigned_abs())
+    } else {
+        (false, bits)
+    };
+    if magnitude > NON_CANONICAL_CONST_GENERIC_MAX {
+        return;
+    }
+    // Negative literals have to be wrapped in a block to be used as a const generic argument.
+    let literal = if negative { format!("{{ -{magnitude} }}") } else { magnitude.to_string() };
+    let span = value.span;
+    lint(tcx, NON_CANONICAL_CONST_GENERIC, cid, span, NonCanonicalConstGeneric { span, literal });
+}
+
+/// Returns whether `expr` only combines integer literals with arithmetic operators, possibly
+/// inside of blocks without statements, like `{ 2 * (3 + 4) }`.
+fn is_literal_arithmetic(expr: &hir::Expr<'_>) -> bool {
+    match expr.kind {
+        hir::ExprKind::Lit(lit) => matches!(lit.node, ast::LitKind::Int(..)),
+        hir::ExprKind::Unary(hir::UnOp::Neg, operand) => is_literal_arithmetic(operand),
+        hir::ExprKind::Binary(op, lhs, rhs) => {
+            matches!(
+                op.node,
+                hir::BinOpKind::Add
+                    | hir::BinOpKind::Sub
+                    | hir::BinOpKind::Mul
+                    | hir::BinOpKind::Div
+                    | hir::BinOpKind::Rem
+            ) && is_literal_arithmetic(lhs)
+                && is_literal_arithmetic(rhs)
+        }
+        hir::ExprKind::Block(hir::Block { stmts: [], expr: Some(expr), .. }, None) => {
+            is_literal_arithmetic(expr)
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether `expr` is an integer literal, possibly negated or inside of blocks without
+/// statements.
+fn is_literal(expr: &hir::Expr<'_>) -> bool {
+    match expr.kind {
+        hir::ExprKind::Lit(_) => true,
+        hir::ExprKind::Unary(hir::UnOp::Neg, operand) => is_literal(operand),
+        hir::ExprKind::Block(hir::Block { stmts: [], expr: Some(expr), .. }, None) => {
+            is_literal(expr)
+        }
+        _ => false,
+    }
+}
+
 // We forbid type-level constants that contain more than `VALTREE_MAX_NODES` nodes.
 const VALTREE_MAX_NODES: usize = 100000;
 
@@ -45,6 +150,20 @@ pub(crate) enum ValTreeCreationError {
 }
 pub(crate) type ValTreeCreationResult<'tcx> = Result<ty::ValTree<'tcx>, ValTreeCreationError>;
 
+/// Turns the final value `alloc` of a constant of type `ty` into a type-level constant value, for
+/// the `shared_valtree` query.
+pub(crate) fn shared_valtree<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    (ty, alloc): (Ty<'tcx>, ConstAllocation<'tcx>),
+) -> Option<(ty::ValTree<'tcx>, usize)> {
+    let ecx = mk_eval_cx(tcx, DUMMY_SP, ty::ParamEnv::reveal_all(), CanAccessStatics::No);
+    let alloc_id = tcx.create_memory_alloc(alloc);
+    let place = ecx.raw_const_to_mplace(ConstAlloc { alloc_id, ty }).ok()?;
+    let mut num_nodes = 0;
+    let valtree = const_to_valtree_inner(&ecx, &place, &mut num_nodes).ok()?;
+    Some((valtree, num_nodes))
+}
+
 /// Evaluates a constant and turns it into a type-level constant value.
 pub(crate) fn eval_to_valtree<'tcx>(
     tcx: TyCtxt<'tcx>,
@@ -66,7 +185,40 @@ pub(crate) fn eval_to_valtree<'tcx>(
     debug!(?place);
 
     let mut num_nodes = 0;
-    let valtree_result = const_to_valtree_inner(&ecx, &place, &mut num_nodes);
+    // Different constants, e.g. different instances of the same generic constant, often produce
+    // equal values, which are interned to the same allocation. Only build their valtree once.
+    let shared = if tcx.sess.opts.unstable_opts.share_const_valtrees
+        && !place.layout.ty.is_scalar()
+        && !place.layout.ty.has_param()
+    {
+        let alloc = tcx.global_alloc(const_alloc.alloc_id).unwrap_memory();
+        tcx.shared_valtree((place.layout.ty, alloc))
+    } else {
+        None
+    };
+    let valtree_result = match shared {
+        Some((valtree, shared_num_nodes)) => {
+            num_nodes = shared_num_nodes;
+            Ok(valtree)
+        }
+        // Values without a valtree are converted again here to report why.
+        None => const_to_valtree_inner(&ecx, &place, &mut num_nodes),
+    };
+
+    if valtree_result.is_ok() && tcx.sess.opts.unstable_opts.const_eval_stats {
+        tcx.sess.code_stats.record_valtree(num_nodes, || cid.display(tcx));
+    }
+    if let Ok(valtree) = valtree_result
+        && tcx.sess.opts.unstable_opts.collect_const_values
+    {
+        tcx.evaluated_const_values.lock().push((cid, place.layout.ty, valtree));
+    }
+
+    if let Ok(ty::ValTree::Leaf(int)) = valtree_result
+        && place.layout.ty.is_integral()
+    {
+        lint_non_canonical_const_generic(tcx, cid, int, place.layout.ty.is_signed());
+    }
 
     match valtree_result {
         Ok(valtree) => Ok(Some(valtree)),
@@ -121,3 +273,382 @@ pub(crate) fn try_destructure_mir_constant<'tcx>(
 
     Ok(mir::DestructuredConstant { variant, fields })
 }
+
+/// Projects to the element at `index` of an array or slice place in a constant. Unlike
+/// `mplace_index`, this also checks `index` against the size of the allocation backing the place,
+/// so that constants with bogus length metadata produce a proper error instead of reading out of
+/// bounds.
+pub(crate) fn const_slice_index<'tcx>(
+    ecx: &CompileTimeEvalContext<'tcx, 'tcx>,
+    place: &MPlaceTy<'tcx>,
+    index: u64,
+) -> InterpResult<'tcx, MPlaceTy<'tcx>> {
+    let FieldsShape::Array { stride, .. } = place.layout.fields else {
+        bug!("`const_slice_index` called on non-array type {:?}", place.layout.ty)
+    };
+    let mut len = place.len(ecx)?;
+    if stride.bytes() > 0
+        && let Ok((alloc_id, offset, _)) = ecx.ptr_try_get_alloc_id(place.ptr)
+    {
+        let (size, _, _) = ecx.get_alloc_info(alloc_id);
+        len = len.min(size.bytes().saturating_sub(offset.bytes()) / stride.bytes());
+    }
+    if index >= len {
+        throw_machine_stop!(ConstEvalErrKind::ConstSliceIndexOutOfBounds { index, len });
+    }
+    ecx.mplace_index(place, index)
+}
+
+/// Reads the element at `index` of an array constant, or of a constant reference to an array or
+/// slice.
+#[instrument(skip(tcx), level = "debug")]
+pub fn try_read_mir_constant_element<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    val: mir::ConstantKind<'tcx>,
+    index: u64,
+) -> InterpResult<'tcx, mir::ConstantKind<'tcx>> {
+    let mut ecx = mk_eval_cx(tcx, DUMMY_SP, param_env, CanAccessStatics::No);
+    let op = ecx.eval_mir_constant(&val, None, None)?;
+    let place = match val.ty().kind() {
+        ty::Ref(..) => ecx.deref_operand(&op)?,
+        ty::Array(..) => match op.as_mplace_or_imm() {
+            Left(mplace) => mplace,
+            Right(imm) => {
+                let place = ecx.allocate(imm.layout, interpret::MemoryKind::Stack)?;
+                ecx.write_immediate(*imm, &place.into())?;
+                place
+            }
+        },
+        _ => bug!("cannot index into mir constant {:?}", val),
+    };
+    let elem = const_slice_index(&ecx, &place, index)?;
+    Ok(mir::ConstantKind::Val(op_to_const(&ecx, &elem.into()), elem.layout.ty))
+}
+
+/// Returns the concrete type behind the vtable of a trait object pointer constant, such as
+/// `const X: &dyn Trait = &CONCRETE;`. Such constants have no valtree, so this is only meant for
+/// inspecting them.
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn dyn_const_concrete_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    const_alloc: ConstAlloc<'tcx>,
+) -> Option<Ty<'tcx>> {
+    let pointee = const_alloc.ty.builtin_deref(true)?.ty;
+    if !matches!(pointee.kind(), ty::Dynamic(..)) {
+        return None;
+    }
+    let ecx = mk_eval_cx(tcx, DUMMY_SP, ty::ParamEnv::reveal_all(), CanAccessStatics::No);
+    let place = ecx.raw_const_to_mplace(const_alloc).ok()?;
+    let (_data, vtable) = ecx.read_immediate(&place.into()).ok()?.to_scalar_pair();
+    let (ty, _trait_ref) = ecx.get_ptr_vtable(vtable.to_pointer(&ecx).ok()?).ok()?;
+    Some(ty)
+}
+
+/// Evaluates a call of the const fn `def_id` with `substs` on the constant arguments `args` as if
+/// it happened in the body of a const item, and returns the result as a type-level constant. The
+/// call can't access statics. Returns `None` if `def_id` isn't a const fn, the arguments don't
+/// match its signature, evaluation fails, or the result can't be represented as a valtree.
+///
+/// If `def_id` is `#[track_caller]`, `caller` is the location it sees as its caller, e.g. in
+/// panic messages. Without a `caller`, that is the definition of `def_id` itself.
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn eval_const_fn_call<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    def_id: DefId,
+    substs: SubstsRef<'tcx>,
+    args: &[ty::Const<'tcx>],
+    caller: Option<Span>,
+) -> Option<ty::Const<'tcx>> {
+    let param_env = param_env.with_const();
+    let instance = resolve_const_fn(tcx, param_env, def_id, substs)?;
+    let root_span = caller.unwrap_or_else(|| tcx.def_span(def_id));
+    let mut ecx = mk_eval_cx(tcx, root_span, param_env, CanAccessStatics::No);
+    let args = args
+        .iter()
+        .map(|&ct| ecx.eval_mir_constant(&mir::ConstantKind::Ty(ct), None, None))
+        .collect::<InterpResult<'tcx, Vec<_>>>()
+        .ok()?;
+    let ret = eval_const_fn_call_using_ecx(&mut ecx, instance, &args).ok()??;
+    let valtree = const_to_valtree_inner(&ecx, &ret, &mut 0).ok()?;
+    Some(ty::Const::new_value(tcx, valtree, ret.layout.ty))
+}
+
+/// Evaluates the const item, associated const or anonymous const `def_id` as if it was used with
+/// the generic arguments `substs`, e.g. to find out what a generic associated const is for
+/// `T = u32`. Returns `None` if `def_id` isn't a constant, if `substs` don't match its generics or
+/// aren't fully monomorphic, or if evaluation fails.
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn eval_const_with_substs<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    substs: SubstsRef<'tcx>,
+) -> Option<ty::Const<'tcx>> {
+    if !matches!(
+        tcx.def_kind(def_id),
+        DefKind::Const | DefKind::AssocConst | DefKind::AnonConst | DefKind::InlineConst
+    ) {
+        return None;
+    }
+    let substs = tcx.erase_regions(substs);
+    if substs.has_non_region_param() || substs.has_infer() || substs.has_placeholders() {
+        return None;
+    }
+    let generics = tcx.generics_of(def_id);
+    if generics.count() != substs.len() {
+        return None;
+    }
+    for (index, arg) in substs.iter().enumerate() {
+        let param = generics.param_at(index, tcx);
+        let matches = match (&param.kind, arg.unpack()) {
+            (ty::GenericParamDefKind::Lifetime, ty::GenericArgKind::Lifetime(_))
+            | (ty::GenericParamDefKind::Type { .. }, ty::GenericArgKind::Type(_)) => true,
+            (ty::GenericParamDefKind::Const { .. }, ty::GenericArgKind::Const(ct)) => {
+                ct.ty() == tcx.type_of(param.def_id).subst(tcx, substs)
+            }
+            _ => false,
+        };
+        if !matches {
+            return None;
+        }
+    }
+    let param_env = ty::ParamEnv::reveal_all();
+    let ty = tcx.type_of(def_id).subst(tcx, substs);
+    let ty = tcx.try_normalize_erasing_regions(param_env, ty).ok()?;
+    let uv = ty::UnevaluatedConst::new(def_id, substs);
+    let ct = ty::Const::new_unevaluated(tcx, uv, ty).eval(tcx, param_env);
+    matches!(ct.kind(), ty::ConstKind::Value(_)).then_some(ct)
+}
+
+/// Evaluates `array.map(f)` for a constant array `array`, where `f` is the type of a const fn item
+/// or of a const closure that doesn't capture anything, and returns the new array as a constant.
+/// All calls of `f` are evaluated with the same interpreter, each like `eval_const_fn_call`
+/// evaluates a single call. Returns `None` if `f` isn't const, or in all the cases where
+/// `eval_const_fn_call` would.
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn eval_const_array_map<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    array: ty::Const<'tcx>,
+    f: Ty<'tcx>,
+) -> Option<ty::Const<'tcx>> {
+    let ty::Array(_, len) = *array.ty().kind() else {
+        return None;
+    };
+    let len = len.try_eval_target_usize(tcx, param_env)?;
+    let (def_id, substs) = match *f.kind() {
+        ty::FnDef(def_id, substs) => (def_id, substs),
+        ty::Closure(def_id, substs) if substs.as_closure().upvar_tys().next().is_none() => {
+            (def_id, substs)
+        }
+        _ => return None,
+    };
+    let param_env = param_env.with_const();
+    let instance = resolve_const_fn(tcx, param_env, def_id, substs)?;
+    let mut ecx = mk_eval_cx(tcx, tcx.def_span(def_id), param_env, CanAccessStatics::No);
+    let (elems, ret_ty) =
+        eval_const_array_map_using_ecx(&mut ecx, instance, array, f, len).ok()??;
+    let valtree = ty::ValTree::Branch(tcx.arena.alloc_from_iter(elems));
+    Some(ty::Const::new_value(tcx, valtree, tcx.mk_array(ret_ty, len)))
+}
+
+fn eval_const_array_map_using_ecx<'tcx>(
+    ecx: &mut CompileTimeEvalContext<'tcx, 'tcx>,
+    instance: ty::Instance<'tcx>,
+    array: ty::Const<'tcx>,
+    f: Ty<'tcx>,
+    len: u64,
+) -> InterpResult<'tcx, Option<(Vec<ty::ValTree<'tcx>>, Ty<'tcx>)>> {
+    let tcx = *ecx.tcx;
+    let body = ecx.load_mir(instance.def, None)?;
+    let ret_ty = instance.subst_mir_and_normalize_erasing_regions(
+        tcx,
+        ecx.param_env,
+        body.bound_return_ty(),
+    );
+    let array = ecx.eval_mir_constant(&mir::ConstantKind::Ty(array), None, None)?;
+
+    // A closure gets its environment as the first argument, by reference unless it is `FnOnce`.
+    let env = match *f.kind() {
+        ty::Closure(_, substs) => {
+            let env = ecx.allocate(ecx.layout_of(f)?, interpret::MemoryKind::Stack)?;
+            Some(match substs.as_closure().kind() {
+                ty::ClosureKind::FnOnce => env.into(),
+                kind => {
+                    let ref_ty = if kind == ty::ClosureKind::Fn {
+                        tcx.mk_imm_ref(tcx.lifetimes.re_erased, f)
+                    } else {
+                        tcx.mk_mut_ref(tcx.lifetimes.re_erased, f)
+                    };
+                    ImmTy::from_immediate(env.to_ref(ecx), ecx.layout_of(ref_ty)?).into()
+                }
+            })
+        }
+        _ => None,
+    };
+
+    let mut elems = Vec::new();
+    for idx in 0..len {
+        let elem = ecx.operand_index(&array, idx)?;
+        let args: Vec<_> = env.iter().cloned().chain([elem]).collect();
+        let Some(ret) = eval_const_fn_call_using_ecx(ecx, instance, &args)? else {
+            return Ok(None);
+        };
+        let Ok(valtree) = const_to_valtree_inner(ecx, &ret, &mut 0) else {
+            return Ok(None);
+        };
+        elems.push(valtree);
+    }
+    Ok(Some((elems, ret_ty)))
+}
+
+/// Resolves the const fn or const closure `def_id` to the instance that calling it with `substs`
+/// would evaluate.
+fn resolve_const_fn<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    def_id: DefId,
+    substs: SubstsRef<'tcx>,
+) -> Option<ty::Instance<'tcx>> {
+    if !tcx.is_const_fn_raw(def_id) {
+        return None;
+    }
+    let instance = ty::Instance::resolve(tcx, param_env, def_id, substs).ok()??;
+    matches!(instance.def, ty::InstanceDef::Item(_)).then_some(instance)
+}
+
+fn eval_const_fn_call_using_ecx<'tcx>(
+    ecx: &mut CompileTimeEvalContext<'tcx, 'tcx>,
+    instance: ty::Instance<'tcx>,
+    args: &[OpTy<'tcx>],
+) -> InterpResult<'tcx, Option<MPlaceTy<'tcx>>> {
+    let body = ecx.load_mir(instance.def, None)?;
+    if body.arg_count != args.len() || body.spread_arg.is_some() {
+        return Ok(None);
+    }
+    let ret_ty = instance.subst_mir_and_normalize_erasing_regions(
+        *ecx.tcx,
+        ecx.param_env,
+        body.bound_return_ty(),
+    );
+    let layout = ecx.layout_of(ret_ty)?;
+    if !layout.is_sized() {
+        return Ok(None);
+    }
+    let ret = ecx.allocate(layout, interpret::MemoryKind::Stack)?;
+    // If `instance` is `#[track_caller]`, its caller is the root span of the evaluation.
+    ecx.machine.root_caller_location = Some(ecx.tcx.span);
+    ecx.push_stack_frame(instance, body, &ret.into(), StackPopCleanup::Root { cleanup: false })?;
+
+    // Pass the arguments the way `eval_fn_call` would, but without a caller frame.
+    let frame = ecx.frame_idx();
+    for (local, arg) in iter::zip(body.args_iter(), args) {
+        let dest = ecx.local_to_place(frame, local)?;
+        if dest.layout.ty != ecx.tcx.erase_regions(arg.layout.ty) {
+            return Ok(None);
+        }
+        ecx.copy_op(arg, &dest, /*allow_transmute*/ false)?;
+    }
+
+    while ecx.step()? {}
+
+    ecx.machine.check_alignment = CheckAlignment::No; // interning doesn't need to respect alignment
+    intern_const_alloc_recursive(ecx, InternKind::Constant, &ret)?;
+    Ok(Some(ret))
+}
+
+/// How the discriminant of an enum is stored in memory, as returned by
+/// `const_enum_discriminant_encoding`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiscriminantEncoding {
+    /// The enum has a single inhabited variant, so no discriminant is stored at all.
+    Single { variant: VariantIdx },
+    /// The discriminant is stored in a separate tag of `size` bytes at `offset`.
+    Tag { offset: Size, size: Size },
+    /// `untagged_variant` is stored as is, and all other variants are encoded as invalid values
+    /// in a niche of field `field` of `untagged_variant`, with a `size`-byte tag at `offset` of
+    /// the enum. A variant with index `v` is encoded as
+    /// `(v - niche_variants.start).wrapping_add(niche_start)`.
+    Niche {
+        untagged_variant: VariantIdx,
+        field: usize,
+        offset: Size,
+        size: Size,
+        niche_variants: RangeInclusive<VariantIdx>,
+        niche_start: u128,
+    },
+}
+
+/// Returns how the discriminant of the enum type `ty` is stored, e.g. to explain the memory of a
+/// constant of that type. This only looks at the layout of `ty`. Returns `None` if `ty` isn't an
+/// enum or has no layout.
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn const_enum_discriminant_encoding<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+) -> Option<DiscriminantEncoding> {
+    if !ty.is_enum() {
+        return None;
+    }
+    let param_env = ty::ParamEnv::reveal_all();
+    let layout = tcx.layout_of(param_env.and(ty)).ok()?;
+    let (tag, tag_encoding, tag_field) = match &layout.variants {
+        Variants::Single { index } => {
+            return Some(DiscriminantEncoding::Single { variant: *index });
+        }
+        Variants::Multiple { tag, tag_encoding, tag_field, .. } => (tag, tag_encoding, *tag_field),
+    };
+    let offset = layout.fields.offset(tag_field);
+    let size = tag.size(&tcx);
+    match tag_encoding {
+        TagEncoding::Direct => Some(DiscriminantEncoding::Tag { offset, size }),
+        TagEncoding::Niche { untagged_variant, niche_variants, niche_start } => {
+            // Find the field of the untagged variant that contains the niche.
+            let cx = LayoutCx { tcx, param_env };
+            let variant = layout.for_variant(&cx, *untagged_variant);
+            let field = (0..variant.fields.count()).find(|&i| {
+                let field_offset = variant.fields.offset(i);
+                field_offset <= offset && offset + size <= field_offset + variant.field(&cx, i).size
+            })?;
+            Some(DiscriminantEncoding::Niche {
+                untagged_variant: *untagged_variant,
+                field,
+                offset,
+                size,
+                niche_variants: niche_variants.clone(),
+                niche_start: *niche_start,
+            })
+        }
+    }
+}
+
+/// Returns the integer type of the tag that stores the discriminant of the enum type `ty`, e.g.
+/// `u8` for a `#[repr(u8)]` enum. The discriminant of a constant of that type, as read by
+/// `Const::try_eval_discriminant`, fits in this type when truncated to its size. For niche-encoded
+/// enums this is the type of the niche, with pointers reported as `usize`. Returns `None` if `ty`
+/// isn't an enum, has no layout or stores no tag at all, see `const_enum_discriminant_encoding`.
+#[instrument(skip(tcx), level = "debug", ret)]
+pub fn const_enum_tag_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+    if !ty.is_enum() {
+        return None;
+    }
+    let layout = tcx.layout_of(ty::ParamEnv::reveal_all().and(ty)).ok()?;
+    match &layout.variants {
+        Variants::Single { .. } => None,
+        Variants::Multiple { tag, .. } => Some(tag.primitive().to_int_ty(tcx)),
+    }
+}
+
+#[instrument(skip(tcx), level = "debug")]
+pub(crate) fn try_read_mir_constant_discriminant<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    val: mir::ConstantKind<'tcx>,
+) -> InterpResult<'tcx, u128> {
+    let ecx = mk_eval_cx(tcx, DUMMY_SP, param_env, CanAccessStatics::No);
+    let op = ecx.eval_mir_constant(&val, None, None)?;
+    let (discr, _) = ecx.read_discriminant(&op)?;
+    let discr_layout = ecx.layout_of(val.ty().discriminant_ty(tcx))?;
+    discr.to_bits(discr_layout.size)
+}
@@ -0,0 +1,35 @@
+//! Helpers for tests of const evaluation that drive the compiler through its API, like the ones in
+//! `tests/ui-fulldeps/const-eval`.
+//!
+//! These let tests evaluate a constant directly instead of going through a UI test, and return
+//! failures as values that can be asserted on.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::interpret::{ErrorHandled, GlobalId};
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Why a constant couldn't be evaluated to a valtree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EvalForTestError {
+    /// The constant depends on generic parameters.
+    TooGeneric,
+    /// Evaluation failed. The error has already been emitted.
+    Reported,
+    /// Evaluation succeeded, but the value has no valtree representation.
+    NoValTree,
+}
+
+/// Evaluates the non-generic constant `def_id` to a valtree, the same way the type system does.
+pub fn eval_to_valtree_for_test<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+) -> Result<ty::ValTree<'tcx>, EvalForTestError> {
+    let cid = GlobalId { instance: ty::Instance::mono(tcx, def_id), promoted: None };
+    let param_env = tcx.param_env_reveal_all_normalized(def_id);
+    match tcx.const_eval_global_id_for_typeck(param_env, cid, None) {
+        Ok(Some(valtree)) => Ok(valtree),
+        Ok(None) => Err(EvalForTestError::NoValTree),
+        Err(ErrorHandled::TooGeneric) => Err(EvalForTestError::TooGeneric),
+        Err(ErrorHandled::Reported(_)) => Err(EvalForTestError::Reported),
+    }
+}
@@ -1,12 +1,14 @@
 use super::eval_queries::{mk_eval_cx, op_to_const};
 use super::machine::CompileTimeEvalContext;
 use super::{ValTreeCreationError, ValTreeCreationResult, VALTREE_MAX_NODES};
-use crate::const_eval::CanAccessStatics;
+use crate::const_eval::{const_slice_index, CanAccessStatics};
 use crate::interpret::{
     intern_const_alloc_recursive, ConstValue, ImmTy, Immediate, InternKind, MemPlaceMeta,
     MemoryKind, PlaceTy, Scalar,
 };
 use crate::interpret::{MPlaceTy, Value};
+use either::{Left, Right};
+use rustc_middle::mir;
 use rustc_middle::ty::{self, ScalarInt, Ty, TyCtxt};
 use rustc_span::source_map::DUMMY_SP;
 use rustc_target::abi::{Align, FieldIdx, VariantIdx, FIRST_VARIANT};
@@ -61,7 +63,9 @@ fn slice_branches<'tcx>(
 
     let mut elems = Vec::with_capacity(n as usize);
     for i in 0..n {
-        let place_elem = ecx.mplace_index(place, i).unwrap();
+        let Ok(place_elem) = const_slice_index(ecx, place, i) else {
+            return Err(ValTreeCreationError::Other);
+        };
         let valtree = const_to_valtree_inner(ecx, &place_elem, num_nodes)?;
         elems.push(valtree);
     }
@@ -156,6 +160,29 @@ pub(crate) fn const_to_valtree_inner<'tcx>(
     }
 }
 
+/// Converts an evaluated constant into a `ValTree`. This is the inverse of
+/// `valtree_to_const_value`.
+#[instrument(skip(tcx), level = "debug", ret)]
+pub(crate) fn const_val_to_valtree<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    val: mir::ConstantKind<'tcx>,
+) -> Option<ty::ValTree<'tcx>> {
+    let mut ecx = mk_eval_cx(tcx, DUMMY_SP, param_env, CanAccessStatics::No);
+    let op = ecx.eval_mir_constant(&val, None, None).ok()?;
+    let place = match op.as_mplace_or_imm() {
+        Left(mplace) => mplace,
+        Right(imm) => {
+            // Scalars and slices are not backed by memory, so we need to put them into some.
+            let place = ecx.allocate(imm.layout, MemoryKind::Stack).ok()?;
+            ecx.write_immediate(*imm, &place.into()).ok()?;
+            place
+        }
+    };
+    let mut num_nodes = 0;
+    const_to_valtree_inner(&ecx, &place, &mut num_nodes).ok()
+}
+
 #[instrument(skip(ecx), level = "debug")]
 fn create_mplace_from_layout<'tcx>(
     ecx: &mut CompileTimeEvalContext<'tcx, 'tcx>,
@@ -12,6 +12,14 @@
 use rustc_span::Span;
 use rustc_target::abi::call::AdjustForForeignAbiError;
 use rustc_target::abi::{Size, WrappingRange};
+use std::path::PathBuf;
+
+#[derive(Diagnostic)]
+#[diag(const_eval_dump_const_deps_failed)]
+pub(crate) struct DumpConstDepsFailed {
+    pub path: PathBuf,
+    pub error: std::io::Error,
+}
 
 #[derive(Diagnostic)]
 #[diag(const_eval_dangling_ptr_in_final)]
@@ -20,6 +28,27 @@ pub(crate) struct DanglingPtrInFinal {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(const_eval_forbidden_const_bytes)]
+#[note]
+pub(crate) struct ForbiddenConstBytes {
+    #[primary_span]
+    pub span: Span,
+    pub name: String,
+    pub pattern: String,
+    pub alloc_id: String,
+    pub offset: u64,
+}
+
+#[derive(Diagnostic)]
+#[diag(const_eval_uninit_bytes_in_final)]
+#[note]
+pub(crate) struct UninitBytesInFinal<'tcx> {
+    #[primary_span]
+    pub span: Span,
+    pub ty: Ty<'tcx>,
+}
+
 #[derive(Diagnostic)]
 #[diag(const_eval_unstable_in_stable)]
 pub(crate) struct UnstableInStable {
@@ -396,6 +425,21 @@ pub struct LiveDrop<'tcx> {
     pub dropped_at: Option<Span>,
 }
 
+#[derive(LintDiagnostic)]
+#[diag(const_eval_non_canonical_const_generic)]
+pub struct NonCanonicalConstGeneric {
+    #[suggestion(code = "{literal}", applicability = "machine-applicable")]
+    pub span: Span,
+    pub literal: String,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(const_eval_repeated_inline_const_eval)]
+#[help]
+pub struct RepeatedInlineConstEval {
+    pub threshold: usize,
+}
+
 #[derive(LintDiagnostic)]
 #[diag(const_eval_align_check_failed)]
 pub struct AlignmentCheckFailed {
@@ -403,6 +447,14 @@ pub struct AlignmentCheckFailed {
     pub required: u64,
     #[subdiagnostic]
     pub frames: Vec<FrameNote>,
+    #[subdiagnostic]
+    pub repeated: Option<AlignmentCheckRepeated>,
+}
+
+#[derive(Subdiagnostic)]
+#[note(const_eval_align_check_repeated)]
+pub struct AlignmentCheckRepeated {
+    pub times: usize,
 }
 
 #[derive(Diagnostic)]
@@ -484,8 +536,8 @@ fn diagnostic_message(&self) -> DiagnosticMessage {
             Ub(msg) => msg.clone().into(),
             Unreachable => const_eval_unreachable,
             BoundsCheckFailed { .. } => const_eval_bounds_check_failed,
-            DivisionByZero => const_eval_division_by_zero,
-            RemainderByZero => const_eval_remainder_by_zero,
+            DivisionByZero(_) => const_eval_division_by_zero,
+            RemainderByZero(_) => const_eval_remainder_by_zero,
             DivisionOverflow => const_eval_division_overflow,
             RemainderOverflow => const_eval_remainder_overflow,
             PointerArithOverflow => const_eval_pointer_arithmetic_overflow,
@@ -526,8 +578,6 @@ fn add_args<G: EmissionGuarantee>(
         match self {
             Ub(_)
             | Unreachable
-            | DivisionByZero
-            | RemainderByZero
             | DivisionOverflow
             | RemainderOverflow
             | PointerArithOverflow
@@ -540,6 +590,9 @@ fn add_args<G: EmissionGuarantee>(
                 builder.set_arg("len", len);
                 builder.set_arg("index", index);
             }
+            DivisionByZero(dividend) | RemainderByZero(dividend) => {
+                builder.set_arg("dividend", dividend);
+            }
             UnterminatedCString(ptr) | InvalidFunctionPointer(ptr) | InvalidVTablePointer(ptr) => {
                 builder.set_arg("pointer", ptr);
             }
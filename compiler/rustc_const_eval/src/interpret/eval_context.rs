@@ -959,6 +959,7 @@ pub fn eval_global(
             self.param_env
         };
         let param_env = param_env.with_const();
+        self.tcx.note_eval_to_allocation_raw(param_env.and(gid));
         let val = self.ctfe_query(span, |tcx| tcx.eval_to_allocation_raw(param_env.and(gid)))?;
         self.raw_const_to_mplace(val)
     }
@@ -28,7 +28,7 @@
     ValueVisitor,
 };
 use crate::const_eval;
-use crate::errors::{DanglingPtrInFinal, UnsupportedUntypedPointer};
+use crate::errors::{DanglingPtrInFinal, UninitBytesInFinal, UnsupportedUntypedPointer};
 
 pub trait CompileTimeMachine<'mir, 'tcx, T> = Machine<
         'mir,
@@ -56,6 +56,9 @@ struct InternVisitor<'rt, 'mir, 'tcx, M: CompileTimeMachine<'mir, 'tcx, const_ev
     /// This field stores whether we are *currently* inside an `UnsafeCell`. This can affect
     /// the intern mode of references we encounter.
     inside_unsafe_cell: bool,
+    /// The type of the first primitive value we found to contain uninitialized bytes. Only
+    /// tracked with `-Z const-check-uninit`.
+    uninit_ty: &'rt mut Option<Ty<'tcx>>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Hash, Eq)]
@@ -141,7 +144,7 @@ fn intern_shallow<'rt, 'mir, 'tcx, M: CompileTimeMachine<'mir, 'tcx, const_eval:
     None
 }
 
-impl<'rt, 'mir, 'tcx, M: CompileTimeMachine<'mir, 'tcx, const_eval::MemoryKind>>
+impl<'rt, 'mir, 'tcx: 'mir, M: CompileTimeMachine<'mir, 'tcx, const_eval::MemoryKind>>
     InternVisitor<'rt, 'mir, 'tcx, M>
 {
     fn intern_shallow(
@@ -152,6 +155,33 @@ fn intern_shallow(
     ) -> Option<IsStaticOrFn> {
         intern_shallow(self.ecx, self.leftover_allocations, alloc_id, mode, ty)
     }
+
+    /// Checks that a primitive value is fully initialized. Aggregates are checked field by field
+    /// as they get walked, so padding is never looked at. Unions are not walked, so their bytes
+    /// are not checked either.
+    fn check_init(&mut self, mplace: &MPlaceTy<'tcx>) -> InterpResult<'tcx> {
+        let ty = mplace.layout.ty;
+        if !matches!(
+            ty.kind(),
+            ty::Bool
+                | ty::Char
+                | ty::Int(_)
+                | ty::Uint(_)
+                | ty::Float(_)
+                | ty::RawPtr(_)
+                | ty::FnPtr(_)
+        ) {
+            return Ok(());
+        }
+        if let Some(alloc) =
+            self.ecx.get_ptr_alloc(mplace.ptr, mplace.layout.size, mplace.layout.align.abi)?
+        {
+            if !alloc.is_initialized() {
+                *self.uninit_ty = Some(ty);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<'rt, 'mir, 'tcx: 'mir, M: CompileTimeMachine<'mir, 'tcx, const_eval::MemoryKind>>
@@ -213,7 +243,7 @@ fn visit_aggregate(
         //
         // We can do this before the checks for interior mutability below, because only references
         // are relevant in that situation, and we're checking if there are any here.
-        if !is_walk_needed(mplace)? {
+        if !is_walk_needed(mplace)? && !self.ecx.tcx.sess.opts.unstable_opts.const_check_uninit {
             return Ok(());
         }
 
@@ -315,6 +345,9 @@ fn visit_value(&mut self, mplace: &MPlaceTy<'tcx>) -> InterpResult<'tcx> {
             }
             Ok(())
         } else {
+            if self.uninit_ty.is_none() && tcx.sess.opts.unstable_opts.const_check_uninit {
+                self.check_init(mplace)?;
+            }
             // Not a reference -- proceed recursively.
             self.walk_value(mplace)
         }
@@ -359,6 +392,7 @@ pub fn intern_const_alloc_recursive<
     // be available in a typed way. They get interned at the end.
     let mut ref_tracking = RefTracking::empty();
     let leftover_allocations = &mut FxIndexSet::default();
+    let mut uninit_ty = None;
 
     // start with the outermost allocation
     intern_shallow(
@@ -380,6 +414,7 @@ pub fn intern_const_alloc_recursive<
             mode,
             leftover_allocations,
             inside_unsafe_cell: false,
+            uninit_ty: &mut uninit_ty,
         }
         .visit_value(&mplace);
         // We deliberately *ignore* interpreter errors here. When there is a problem, the remaining
@@ -398,6 +433,11 @@ pub fn intern_const_alloc_recursive<
         }
     }
 
+    if let Some(ty) = uninit_ty {
+        let reported = ecx.tcx.sess.emit_err(UninitBytesInFinal { span: ecx.tcx.span, ty });
+        return Err(reported);
+    }
+
     // Intern the rest of the allocations as mutable. These might be inside unions, padding, raw
     // pointers, ... So we can't intern them according to their type rules
 
@@ -206,10 +206,14 @@ pub fn emulate_intrinsic(
                     other => (false, other),
                 };
                 if nonzero && bits == 0 {
-                    throw_ub_custom!(
-                        fluent::const_eval_call_nonzero_intrinsic,
-                        name = intrinsic_name,
-                    );
+                    M::recoverable_ub(
+                        self,
+                        err_ub_custom!(
+                            fluent::const_eval_call_nonzero_intrinsic,
+                            name = intrinsic_name,
+                        )
+                        .into(),
+                    )?;
                 }
                 let out_val = numeric_intrinsic(actual_intrinsic_name, bits, kind);
                 self.write_scalar(out_val, dest)?;
@@ -486,7 +490,10 @@ pub(super) fn emulate_nondiverging_intrinsic(
                 let op = self.eval_operand(op, None)?;
                 let cond = self.read_scalar(&op)?.to_bool()?;
                 if !cond {
-                    throw_ub_custom!(fluent::const_eval_assume_false);
+                    M::recoverable_ub(
+                        self,
+                        err_ub_custom!(fluent::const_eval_assume_false).into(),
+                    )?;
                 }
                 Ok(())
             }
@@ -515,11 +522,15 @@ pub fn exact_div(
         let (res, overflow, _ty) = self.overflowing_binary_op(BinOp::Rem, &a, &b)?;
         assert!(!overflow); // All overflow is UB, so this should never return on overflow.
         if res.assert_bits(a.layout.size) != 0 {
-            throw_ub_custom!(
-                fluent::const_eval_exact_div_has_remainder,
-                a = format!("{a}"),
-                b = format!("{b}")
-            )
+            M::recoverable_ub(
+                self,
+                err_ub_custom!(
+                    fluent::const_eval_exact_div_has_remainder,
+                    a = format!("{a}"),
+                    b = format!("{b}")
+                )
+                .into(),
+            )?;
         }
         // `Rem` says this is all right, so we can let `Div` do its job.
         self.binop_ignore_overflow(BinOp::Div, &a, &b, dest)
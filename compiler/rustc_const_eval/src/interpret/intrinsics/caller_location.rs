@@ -11,7 +11,8 @@
 
 impl<'mir, 'tcx: 'mir, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
     /// Walks up the callstack from the intrinsic's callsite, searching for the first callsite in a
-    /// frame which is not `#[track_caller]`.
+    /// frame which is not `#[track_caller]`. If every frame is `#[track_caller]`, the machine
+    /// decides the location through `Machine::root_caller_location`.
     pub(crate) fn find_closest_untracked_caller_location(&self) -> Span {
         for frame in self.stack().iter().rev() {
             debug!("find_closest_untracked_caller_location: checking frame {:?}", frame.instance);
@@ -67,7 +68,8 @@ pub(crate) fn find_closest_untracked_caller_location(&self) -> Span {
             }
         }
 
-        span_bug!(self.cur_span(), "no non-`#[track_caller]` frame found")
+        M::root_caller_location(self)
+            .unwrap_or_else(|| span_bug!(self.cur_span(), "no non-`#[track_caller]` frame found"))
     }
 
     /// Allocate a `const core::panic::Location` with the provided filename and line/column numbers.
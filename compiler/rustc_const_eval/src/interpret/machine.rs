@@ -11,6 +11,7 @@
 use rustc_middle::ty::layout::TyAndLayout;
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_span::def_id::DefId;
+use rustc_span::Span;
 use rustc_target::abi::{Align, Size};
 use rustc_target::spec::abi::Abi as CallAbi;
 
@@ -18,7 +19,7 @@
 
 use super::{
     AllocBytes, AllocId, AllocRange, Allocation, ConstAllocation, Frame, ImmTy, InterpCx,
-    InterpResult, MemoryKind, OpTy, Operand, PlaceTy, Pointer, Provenance, Scalar,
+    InterpErrorInfo, InterpResult, MemoryKind, OpTy, Operand, PlaceTy, Pointer, Provenance, Scalar,
 };
 
 /// Data returned by Machine::stack_pop,
@@ -146,6 +147,17 @@ fn alignment_check_failed(
         check: CheckAlignment,
     ) -> InterpResult<'tcx, ()>;
 
+    /// Called when the program has UB that the interpreter knows how to continue after, like an
+    /// unchecked arithmetic operation that overflowed, whose result then just wraps around.
+    /// Returning `Ok(())` continues evaluation; by default, `err` is raised.
+    #[inline]
+    fn recoverable_ub(
+        _ecx: &InterpCx<'mir, 'tcx, Self>,
+        err: InterpErrorInfo<'tcx>,
+    ) -> InterpResult<'tcx> {
+        Err(err)
+    }
+
     /// Whether to enforce the validity invariant for a specific layout.
     fn enforce_validity(ecx: &InterpCx<'mir, 'tcx, Self>, layout: TyAndLayout<'tcx>) -> bool;
 
@@ -251,6 +263,15 @@ fn access_local_mut<'a>(
         ecx.stack_mut()[frame].locals[local].access_mut()
     }
 
+    /// Called after a statement has been executed, before the statement counter is advanced.
+    #[inline]
+    fn after_statement(
+        _ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        _stmt: &mir::Statement<'tcx>,
+    ) -> InterpResult<'tcx> {
+        Ok(())
+    }
+
     /// Called before a basic block terminator is executed.
     #[inline]
     fn before_terminator(_ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
@@ -356,6 +377,24 @@ fn eval_inline_asm(
         throw_unsup_format!("inline assembly is not supported")
     }
 
+    /// Returns the caller location seen by `#[track_caller]` functions when every frame on the
+    /// stack is `#[track_caller]`, i.e. when evaluation was started directly on such a function.
+    /// Machines that never do that return `None`.
+    #[inline(always)]
+    fn root_caller_location(_ecx: &InterpCx<'mir, 'tcx, Self>) -> Option<Span> {
+        None
+    }
+
+    /// Hook for performing extra checks before a new allocation of the given size is created.
+    #[inline(always)]
+    fn before_memory_allocation(
+        _ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        _size: Size,
+        _kind: MemoryKind<Self::MemoryKind>,
+    ) -> InterpResult<'tcx> {
+        Ok(())
+    }
+
     /// Hook for performing extra checks on a memory read access.
     ///
     /// Takes read-only access to the allocation so we can keep all the memory read
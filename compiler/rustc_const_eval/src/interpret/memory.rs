@@ -226,6 +226,7 @@ pub fn allocate_raw_ptr(
         alloc: Allocation,
         kind: MemoryKind<M::MemoryKind>,
     ) -> InterpResult<'tcx, Pointer<M::Provenance>> {
+        M::before_memory_allocation(self, alloc.size(), kind)?;
         let id = self.tcx.reserve_alloc_id();
         debug_assert_ne!(
             Some(kind),
@@ -1020,6 +1021,11 @@ pub fn get_bytes_strip_provenance<'b>(&'b self) -> InterpResult<'tcx, &'a [u8]>
     pub(crate) fn has_provenance(&self) -> bool {
         !self.alloc.provenance().range_empty(self.range, &self.tcx)
     }
+
+    /// Returns whether every byte in the range of the `AllocRef` is initialized.
+    pub(crate) fn is_initialized(&self) -> bool {
+        self.alloc.init_mask().is_range_initialized(self.range).is_ok()
+    }
 }
 
 impl<'mir, 'tcx: 'mir, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
@@ -2,7 +2,7 @@
 use rustc_middle::mir;
 use rustc_middle::mir::interpret::{InterpResult, Scalar};
 use rustc_middle::ty::layout::{LayoutOf, TyAndLayout};
-use rustc_middle::ty::{self, FloatTy, Ty};
+use rustc_middle::ty::{self, ConstInt, FloatTy, ScalarInt, Ty};
 use rustc_span::symbol::sym;
 use rustc_target::abi::Abi;
 
@@ -194,11 +194,15 @@ fn binary_int_op(
             let truncated = self.truncate(result, left_layout);
 
             if overflow && let Some(intrinsic_name) = throw_ub_on_overflow {
-                throw_ub_custom!(
-                    fluent::const_eval_overflow_shift,
-                    val = original_r,
-                    name = intrinsic_name
-                );
+                M::recoverable_ub(
+                    self,
+                    err_ub_custom!(
+                        fluent::const_eval_overflow_shift,
+                        val = original_r,
+                        name = intrinsic_name
+                    )
+                    .into(),
+                )?;
             }
 
             return Ok((Scalar::from_uint(truncated, left_layout.size), overflow, left_layout.ty));
@@ -218,6 +222,30 @@ fn binary_int_op(
         }
 
         let size = left_layout.size;
+        // The operands, for error messages about dividing by zero or overflowing.
+        let operand = |bits: u128| {
+            ConstInt::new(
+                ScalarInt::try_from_uint(bits, size).unwrap(),
+                left_layout.abi.is_signed(),
+                left_layout.ty.is_ptr_sized_integral(),
+            )
+        };
+        let dividend = || operand(l);
+        let overflow_ub = |name| {
+            let op = match bin_op {
+                AddUnchecked => "+",
+                SubUnchecked => "-",
+                MulUnchecked => "*",
+                _ => bug!("no overflow UB for {:?}", bin_op),
+            };
+            err_ub_custom!(
+                fluent::const_eval_overflow,
+                name = name,
+                left = format!("{:#?}", operand(l)),
+                op = op,
+                right = format!("{:#?}", operand(r)),
+            )
+        };
 
         // Operations that need special treatment for signed integers
         if left_layout.abi.is_signed() {
@@ -234,8 +262,8 @@ fn binary_int_op(
                 return Ok((Scalar::from_bool(op(&l, &r)), false, self.tcx.types.bool));
             }
             let op: Option<fn(i128, i128) -> (i128, bool)> = match bin_op {
-                Div if r == 0 => throw_ub!(DivisionByZero),
-                Rem if r == 0 => throw_ub!(RemainderByZero),
+                Div if r == 0 => throw_ub!(DivisionByZero(dividend())),
+                Rem if r == 0 => throw_ub!(RemainderByZero(dividend())),
                 Div => Some(i128::overflowing_div),
                 Rem => Some(i128::overflowing_rem),
                 Add | AddUnchecked => Some(i128::overflowing_add),
@@ -266,7 +294,7 @@ fn binary_int_op(
                 let truncated = self.truncate(result, left_layout);
                 let overflow = oflo || self.sign_extend(truncated, left_layout) != result;
                 if overflow && let Some(intrinsic_name) = throw_ub_on_overflow {
-                    throw_ub_custom!(fluent::const_eval_overflow, name = intrinsic_name);
+                    M::recoverable_ub(self, overflow_ub(intrinsic_name).into())?;
                 }
                 return Ok((Scalar::from_uint(truncated, size), overflow, left_layout.ty));
             }
@@ -291,8 +319,8 @@ fn binary_int_op(
                     Add | AddUnchecked => u128::overflowing_add,
                     Sub | SubUnchecked => u128::overflowing_sub,
                     Mul | MulUnchecked => u128::overflowing_mul,
-                    Div if r == 0 => throw_ub!(DivisionByZero),
-                    Rem if r == 0 => throw_ub!(RemainderByZero),
+                    Div if r == 0 => throw_ub!(DivisionByZero(dividend())),
+                    Rem if r == 0 => throw_ub!(RemainderByZero(dividend())),
                     Div => u128::overflowing_div,
                     Rem => u128::overflowing_rem,
                     _ => bug!(),
@@ -303,7 +331,7 @@ fn binary_int_op(
                 let truncated = self.truncate(result, left_layout);
                 let overflow = oflo || truncated != result;
                 if overflow && let Some(intrinsic_name) = throw_ub_on_overflow {
-                    throw_ub_custom!(fluent::const_eval_overflow, name = intrinsic_name);
+                    M::recoverable_ub(self, overflow_ub(intrinsic_name).into())?;
                 }
                 return Ok((Scalar::from_uint(truncated, size), overflow, left_layout.ty));
             }
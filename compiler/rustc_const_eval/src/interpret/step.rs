@@ -35,6 +35,7 @@ pub fn step(&mut self) -> InterpResult<'tcx, bool> {
         if let Some(stmt) = basic_block.statements.get(loc.statement_index) {
             let old_frames = self.frame_idx();
             self.statement(stmt)?;
+            M::after_statement(self, stmt)?;
             // Make sure we are not updating `statement_index` of the wrong frame.
             assert_eq!(old_frames, self.frame_idx());
             // Advance the program counter.
@@ -48,6 +48,14 @@ pub fn provide(providers: &mut Providers) {
     providers.eval_to_const_value_raw = const_eval::eval_to_const_value_raw_provider;
     providers.eval_to_allocation_raw = const_eval::eval_to_allocation_raw_provider;
     providers.const_caller_location = const_eval::const_caller_location;
+    providers.const_is_target_dependent = const_eval::const_is_target_dependent_provider;
+    providers.const_is_valid = const_eval::const_is_valid_provider;
+    providers.const_eval_pretty = const_eval::const_eval_pretty_provider;
+    providers.const_allocation_align = const_eval::const_allocation_align_provider;
+    providers.const_used_intrinsics = const_eval::const_used_intrinsics_provider;
+    providers.const_referenced_statics = const_eval::const_referenced_statics_provider;
+    providers.const_shape = const_eval::const_shape_provider;
+    providers.const_minimal_int_ty = const_eval::const_minimal_int_ty_provider;
     providers.eval_to_valtree = |tcx, param_env_and_value| {
         let (param_env, raw) = param_env_and_value.into_parts();
         const_eval::eval_to_valtree(tcx, param_env, raw)
@@ -56,9 +64,18 @@ pub fn provide(providers: &mut Providers) {
         let (param_env, value) = param_env_and_value.into_parts();
         const_eval::try_destructure_mir_constant(tcx, param_env, value).ok()
     };
+    providers.try_read_mir_constant_discriminant = |tcx, param_env_and_value| {
+        let (param_env, value) = param_env_and_value.into_parts();
+        const_eval::try_read_mir_constant_discriminant(tcx, param_env, value).ok()
+    };
+    providers.shared_valtree = const_eval::shared_valtree;
     providers.valtree_to_const_val = |tcx, (ty, valtree)| {
         const_eval::valtree_to_const_value(tcx, ty::ParamEnv::empty().and(ty), valtree)
     };
+    providers.const_val_to_valtree = |tcx, param_env_and_value| {
+        let (param_env, value) = param_env_and_value.into_parts();
+        const_eval::const_val_to_valtree(tcx, param_env, value)
+    };
     providers.check_validity_requirement = |tcx, (init_kind, param_env_and_ty)| {
         util::check_validity_requirement(tcx, init_kind, param_env_and_ty)
     };
@@ -434,9 +434,13 @@ fn run_compiler(
                 return early_exit();
             }
 
-            queries.global_ctxt()?.enter(|tcx| tcx.analysis(()))?;
+            if let Err(err) = queries.global_ctxt()?.enter(|tcx| tcx.analysis(())) {
+                dump_const_deps(queries)?;
+                return Err(err);
+            }
 
             if callbacks.after_analysis(&handler, compiler, queries) == Compilation::Stop {
+                dump_const_deps(queries)?;
                 return early_exit();
             }
 
@@ -453,6 +457,16 @@ fn run_compiler(
                 sess.code_stats.print_vtable_sizes(crate_name);
             }
 
+            if sess.opts.unstable_opts.print_large_consts.is_some() {
+                sess.code_stats.print_large_consts();
+            }
+
+            if sess.opts.unstable_opts.const_eval_stats {
+                sess.code_stats.print_valtree_stats();
+            }
+
+            dump_const_deps(queries)?;
+
             let linker = queries.linker(ongoing_codegen)?;
             Ok(Some(linker))
         })?;
@@ -478,6 +492,14 @@ fn run_compiler(
     })
 }
 
+/// Writes the graph requested with `-Z dump-const-deps`. This waits until the session is about to
+/// end, so that constants evaluated during codegen, like promoteds and generic constants, are
+/// included.
+fn dump_const_deps<'tcx>(queries: &'tcx Queries<'tcx>) -> interface::Result<()> {
+    queries.global_ctxt()?.enter(|tcx| rustc_const_eval::const_eval::dump_const_deps(tcx));
+    Ok(())
+}
+
 // Extract output directory and file from matches.
 fn make_output(matches: &getopts::Matches) -> (Option<PathBuf>, Option<OutFileName>) {
     let odir = matches.opt_str("out-dir").map(|o| PathBuf::from(&o));
@@ -11,6 +11,7 @@
     ty::{self, error::TypeError, print::FmtPrinter, suggest_constraining_type_param, Ty},
 };
 use rustc_span::{def_id::DefId, sym, BytePos, Span, Symbol};
+use std::iter;
 
 impl<'tcx> TypeErrCtxt<'_, 'tcx> {
     pub fn note_and_explain_type_err(
@@ -276,6 +277,11 @@ fn foo(&self, x: T) -> T { x }
                     }
                     _ => {}
                 }
+                self.note_structurally_different_consts(
+                    diag,
+                    values.expected.into(),
+                    values.found.into(),
+                );
                 debug!(
                     "note_and_explain_type_err expected={:?} ({:?}) found={:?} ({:?})",
                     values.expected,
@@ -284,6 +290,13 @@ fn foo(&self, x: T) -> T { x }
                     values.found.kind(),
                 );
             }
+            ConstMismatch(values) => {
+                self.note_structurally_different_consts(
+                    diag,
+                    values.expected.into(),
+                    values.found.into(),
+                );
+            }
             CyclicTy(ty) => {
                 // Watch out for various cases of cyclic types and try to explain.
                 if ty.is_closure() || ty.is_generator() {
@@ -306,6 +319,40 @@ fn foo(&self, x: T) -> T { x }
         }
     }
 
+    /// Const generic arguments are equal only if their valtrees are, but printing a valtree may
+    /// lose information (e.g. invalid UTF-8 in a `&str`). If the mismatch contains two consts
+    /// that print the same but are structurally different, point that out, as the error would
+    /// otherwise read as "expected `X`, found `X`".
+    fn note_structurally_different_consts(
+        &self,
+        diag: &mut Diagnostic,
+        expected: ty::GenericArg<'tcx>,
+        found: ty::GenericArg<'tcx>,
+    ) {
+        for (expected, found) in iter::zip(expected.walk(), found.walk()) {
+            let (ty::GenericArgKind::Const(expected), ty::GenericArgKind::Const(found)) =
+                (expected.unpack(), found.unpack())
+            else {
+                continue;
+            };
+            if let (ty::ConstKind::Value(expected_val), ty::ConstKind::Value(found_val)) =
+                (expected.kind(), found.kind())
+                && expected_val != found_val
+                && expected.ty() == found.ty()
+                && expected.to_string() == found.to_string()
+            {
+                diag.note(format!(
+                    "const generic arguments are compared by their full structural \
+                     representation, and the two values of type `{}` printed as `{}` differ in \
+                     a way that is not shown",
+                    expected.ty(),
+                    expected,
+                ));
+                return;
+            }
+        }
+    }
+
     fn suggest_constraint(
         &self,
         diag: &mut Diagnostic,
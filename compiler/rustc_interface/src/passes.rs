@@ -6,6 +6,7 @@
 use rustc_ast::{self as ast, visit};
 use rustc_borrowck as mir_borrowck;
 use rustc_codegen_ssa::traits::CodegenBackend;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::parallel;
 use rustc_data_structures::steal::Steal;
 use rustc_data_structures::sync::{Lrc, OnceCell, WorkerLocal};
@@ -17,6 +18,7 @@
 use rustc_metadata::creader::CStore;
 use rustc_middle::arena::Arena;
 use rustc_middle::dep_graph::DepGraph;
+use rustc_middle::mir::interpret::{GlobalAlloc, GlobalId};
 use rustc_middle::query::{ExternProviders, Providers};
 use rustc_middle::ty::{self, GlobalCtxt, RegisteredTools, TyCtxt};
 use rustc_mir_build as mir_build;
@@ -24,7 +26,7 @@
 use rustc_passes::{self, hir_stats, layout_test};
 use rustc_plugin_impl as plugin;
 use rustc_resolve::Resolver;
-use rustc_session::code_stats::VTableSizeInfo;
+use rustc_session::code_stats::{ConstSizeInfo, VTableSizeInfo};
 use rustc_session::config::{CrateType, Input, OutFileName, OutputFilenames, OutputType};
 use rustc_session::cstore::{MetadataLoader, Untracked};
 use rustc_session::output::filename_for_input;
@@ -32,6 +34,7 @@
 use rustc_session::{Limit, Session};
 use rustc_span::symbol::{sym, Symbol};
 use rustc_span::FileName;
+use rustc_target::abi::Size;
 use rustc_target::spec::PanicStrategy;
 use rustc_trait_selection::traits;
 
@@ -953,6 +956,44 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
         }
     }
 
+    if let Some(threshold) = sess.opts.unstable_opts.print_large_consts {
+        for def_id in tcx.hir().body_owners() {
+            if !matches!(
+                tcx.def_kind(def_id),
+                rustc_hir::def::DefKind::Const | rustc_hir::def::DefKind::AssocConst
+            ) || tcx.generics_of(def_id).requires_monomorphization(tcx)
+            {
+                continue;
+            }
+
+            let def_id = def_id.to_def_id();
+            let cid = GlobalId { instance: ty::Instance::mono(tcx, def_id), promoted: None };
+            let param_env = tcx.param_env_reveal_all_normalized(def_id);
+            // Evaluation errors are reported by the query itself.
+            let Ok(alloc) = tcx.eval_to_allocation_raw(param_env.and(cid)) else { continue };
+
+            // Count everything reachable from the constant's allocation, as all of it ends up
+            // in the binary when the constant is used.
+            let mut size = Size::ZERO;
+            let mut seen = FxHashSet::default();
+            let mut todo = vec![alloc.alloc_id];
+            while let Some(alloc_id) = todo.pop() {
+                if !seen.insert(alloc_id) {
+                    continue;
+                }
+                if let Some(GlobalAlloc::Memory(alloc)) = tcx.try_get_global_alloc(alloc_id) {
+                    size += alloc.inner().size();
+                    todo.extend(alloc.inner().provenance().ptrs().values().copied());
+                }
+            }
+
+            if size.bytes() > threshold {
+                let def_path = ty::print::with_no_trimmed_paths!(tcx.def_path_str(def_id));
+                sess.code_stats.record_const_size(ConstSizeInfo { def_path, size: size.bytes() });
+            }
+        }
+    }
+
     Ok(())
 }
 
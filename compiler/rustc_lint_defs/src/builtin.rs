@@ -3366,6 +3366,7 @@
         MISSING_FRAGMENT_SPECIFIER,
         MUST_NOT_SUSPEND,
         NAMED_ARGUMENTS_USED_POSITIONALLY,
+        NON_CANONICAL_CONST_GENERIC,
         NON_EXHAUSTIVE_OMITTED_PATTERNS,
         NONTRIVIAL_STRUCTURAL_MATCH,
         ORDER_DEPENDENT_TRAIT_OBJECTS,
@@ -3379,6 +3380,7 @@
         PROC_MACRO_DERIVE_RESOLUTION_FALLBACK,
         PUB_USE_OF_PRIVATE_EXTERN_CRATE,
         RENAMED_AND_REMOVED_LINTS,
+        REPEATED_INLINE_CONST_EVAL,
         REPR_TRANSPARENT_EXTERNAL_PRIVATE_FIELDS,
         RUST_2021_INCOMPATIBLE_CLOSURE_CAPTURES,
         RUST_2021_INCOMPATIBLE_OR_PATTERNS,
@@ -3469,6 +3471,73 @@
     report_in_external_macro
 }
 
+declare_lint! {
+    /// The `non_canonical_const_generic` lint detects const generic arguments that compute a
+    /// small integer from literals, where the resulting literal would be clearer.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(non_canonical_const_generic)]
+    ///
+    /// struct Buf<const N: usize>([u8; N]);
+    ///
+    /// fn four() -> Buf<{ 2 + 2 }> {
+    ///     Buf([0; 4])
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Const generic arguments are compared by their values, so `Buf<{ 2 + 2 }>` is the same type
+    /// as `Buf<4>`. Spelling out the value makes that obvious to readers and in error messages.
+    /// The lint only fires for arithmetic on integer literals with a result of at most 999, as
+    /// larger values are often clearer as a computation.
+    pub NON_CANONICAL_CONST_GENERIC,
+    Allow,
+    "detects const generic arguments that compute a small integer from literals"
+}
+
+declare_lint! {
+    /// The `repeated_inline_const_eval` lint detects inline and anonymous constants that are
+    /// evaluated for many different instances of the generic item they are part of.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs more than 64 instances of `table`)
+    /// #![feature(inline_const)]
+    /// #![warn(repeated_inline_const_eval)]
+    ///
+    /// fn table<T>() -> &'static [u32; 256] {
+    ///     &const { crc32_table() }
+    /// }
+    /// ```
+    ///
+    /// This will produce:
+    ///
+    /// ```text
+    /// warning: this constant is evaluated separately for more than 64 instances of its parent
+    ///  --> lint_example.rs:5:12
+    ///   |
+    /// 5 |     &const { crc32_table() }
+    ///   |            ^^^^^^^^^^^^^^^^^
+    ///   |
+    ///   = help: if it doesn't depend on generic parameters, consider moving it into a `const` item
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// Inline and anonymous constants inherit the generic parameters of the item they appear
+    /// in, so they are evaluated again for every instance of that item, even if their value
+    /// doesn't depend on the parameters. For expensive constants this can dominate compile
+    /// time. A named `const` item without generic parameters is only evaluated once.
+    pub REPEATED_INLINE_CONST_EVAL,
+    Allow,
+    "detects inline constants that are evaluated for many instances of their parent"
+}
+
 declare_lint! {
     /// The `unused_associated_type_bounds` lint is emitted when an
     /// associated type bound is added to a trait object, but the associated
@@ -73,6 +73,10 @@ middle_strict_coherence_needs_negative_coherence =
     to use `strict_coherence` on this trait, the `with_negative_coherence` feature must be enabled
     .label = due to this attribute
 
+middle_unresolved_const =
+    could not resolve `{$name}` to a value
+    .note = no impl providing this constant applies to these types
+
 middle_unknown_layout =
     the type `{$ty}` has an unknown layout
 
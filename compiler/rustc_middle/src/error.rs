@@ -85,6 +85,15 @@ pub(crate) struct RequiresLangItem {
     pub name: Symbol,
 }
 
+#[derive(Diagnostic)]
+#[diag(middle_unresolved_const)]
+#[note]
+pub(crate) struct UnresolvedConst {
+    #[primary_span]
+    pub span: Span,
+    pub name: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(middle_const_not_used_in_type_alias)]
 pub(super) struct ConstNotUsedTraitAlias {
@@ -2,6 +2,8 @@
 
 mod init_mask;
 mod provenance_map;
+#[cfg(test)]
+mod tests;
 
 use std::borrow::Cow;
 use std::fmt;
@@ -13,7 +15,9 @@
 use either::{Left, Right};
 
 use rustc_ast::Mutability;
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::intern::Interned;
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_span::DUMMY_SP;
 use rustc_target::abi::{Align, HasDataLayout, Size};
 
@@ -392,6 +396,89 @@ pub fn inspect_with_uninit_and_ptr_outside_interpreter(&self, range: Range<usize
         &self.bytes[range]
     }
 
+    /// Returns the byte at `offset`, or `None` if `offset` is out of bounds, or the byte is
+    /// uninitialized or part of a pointer. Like `inspect_with_uninit_and_ptr_outside_interpreter`,
+    /// this must not be used for reads affecting the interpreter execution.
+    pub fn inspect_byte(&self, offset: Size, cx: &impl HasDataLayout) -> Option<u8> {
+        if offset >= self.size()
+            || !self.init_mask.get(offset)
+            || self.provenance.get(offset, cx).is_some()
+        {
+            return None;
+        }
+        Some(self.bytes[offset.bytes_usize()])
+    }
+
+    /// Returns whether the bytes in `range` of `self` and in `other_range` of `other` are the
+    /// same, including which of them are initialized and the provenance of the pointers stored in
+    /// them. The values of uninitialized bytes are ignored. Like `inspect_byte`, this must not be
+    /// used for reads affecting the interpreter execution.
+    pub fn range_bytes_eq(
+        &self,
+        range: AllocRange,
+        other: &Self,
+        other_range: AllocRange,
+        cx: &impl HasDataLayout,
+    ) -> bool
+    where
+        Prov: PartialEq,
+    {
+        if range.size != other_range.size
+            || range.end() > self.size()
+            || other_range.end() > other.size()
+        {
+            return false;
+        }
+        (0..range.size.bytes()).map(Size::from_bytes).all(|i| {
+            let (offset, other_offset) = (range.start + i, other_range.start + i);
+            let init = self.init_mask.get(offset);
+            init == other.init_mask.get(other_offset)
+                && self.provenance.get(offset, cx) == other.provenance.get(other_offset, cx)
+                && (!init
+                    || self.bytes[offset.bytes_usize()] == other.bytes[other_offset.bytes_usize()])
+        })
+    }
+
+    /// Returns a hash of the contents of `range` that is stable across compilations and hosts, or
+    /// `None` if `range` is out of bounds or contains (parts of) pointers. The hash is computed
+    /// with `StableHasher`, i.e. 128-bit SipHash-1-3 with all-zero keys, over the size of `range`
+    /// as a `u64`, followed by `0u8` for each uninitialized byte and `1u8` and the byte itself for
+    /// each initialized one. The values of uninitialized bytes are ignored. Like `inspect_byte`,
+    /// this must not be used for reads affecting the interpreter execution.
+    pub fn range_content_hash(&self, range: AllocRange, cx: &impl HasDataLayout) -> Option<u128> {
+        if range.end() > self.size() || !self.provenance.range_empty(range, cx) {
+            return None;
+        }
+        let mut hasher = StableHasher::new();
+        range.size.bytes().hash(&mut hasher);
+        for offset in (range.start.bytes()..range.end().bytes()).map(Size::from_bytes) {
+            if self.init_mask.get(offset) {
+                (1u8, self.bytes[offset.bytes_usize()]).hash(&mut hasher);
+            } else {
+                0u8.hash(&mut hasher);
+            }
+        }
+        Some(u128::from_le_bytes(hasher.finish::<Fingerprint>().to_le_bytes()))
+    }
+
+    /// Returns the offset of the first occurrence of `pattern` in `self`, only considering
+    /// initialized bytes that aren't part of a pointer. Returns `None` if there is no such
+    /// occurrence or if `pattern` is empty. Like `inspect_byte`, this must not be used for reads
+    /// affecting the interpreter execution.
+    pub fn find_bytes(&self, pattern: &[u8], cx: &impl HasDataLayout) -> Option<Size> {
+        if pattern.is_empty() {
+            return None;
+        }
+        let len = Size::from_bytes(pattern.len());
+        self.bytes.windows(pattern.len()).enumerate().find_map(|(start, window)| {
+            let range = alloc_range(Size::from_bytes(start), len);
+            (window == pattern
+                && self.init_mask.is_range_initialized(range).is_ok()
+                && self.provenance.range_empty(range, cx))
+            .then_some(range.start)
+        })
+    }
+
     /// Returns the mask indicating which bytes are initialized.
     pub fn init_mask(&self) -> &InitMask {
         &self.init_mask
@@ -0,0 +1,143 @@
+use super::*;
+use crate::mir::interpret::alloc_range;
+use rustc_target::abi::TargetDataLayout;
+use std::num::NonZeroU64;
+
+#[test]
+fn inspect_byte() {
+    let cx = TargetDataLayout::default();
+
+    let alloc: Allocation = Allocation::from_bytes_byte_aligned_immutable(&[1u8, 2, 3][..]);
+    assert_eq!(alloc.inspect_byte(Size::ZERO, &cx), Some(1));
+    assert_eq!(alloc.inspect_byte(Size::from_bytes(2), &cx), Some(3));
+    assert_eq!(alloc.inspect_byte(Size::from_bytes(3), &cx), None);
+    assert_eq!(alloc.inspect_byte(Size::from_bytes(u64::MAX), &cx), None);
+
+    let ptr_size = cx.pointer_size;
+    let mut alloc: Allocation = Allocation::uninit(ptr_size * 2, Align::ONE);
+    let one = Size::from_bytes(1);
+    alloc.write_scalar(&cx, alloc_range(ptr_size, one), Scalar::from_u8(7)).unwrap();
+    assert_eq!(alloc.inspect_byte(Size::ZERO, &cx), None);
+    assert_eq!(alloc.inspect_byte(ptr_size, &cx), Some(7));
+    assert_eq!(alloc.inspect_byte(ptr_size + one, &cx), None);
+
+    // None of the bytes of a pointer can be inspected, even though they are initialized.
+    let ptr = Pointer::new(AllocId(NonZeroU64::new(1).unwrap()), Size::ZERO);
+    alloc
+        .write_scalar(&cx, alloc_range(Size::ZERO, ptr_size), Scalar::from_pointer(ptr, &cx))
+        .unwrap();
+    assert_eq!(alloc.inspect_byte(Size::ZERO, &cx), None);
+    assert_eq!(alloc.inspect_byte(ptr_size - one, &cx), None);
+    assert_eq!(alloc.inspect_byte(ptr_size, &cx), Some(7));
+}
+
+#[test]
+fn range_bytes_eq_compares_provenance() {
+    let cx = TargetDataLayout::default();
+    let ptr_size = cx.pointer_size;
+    let whole = alloc_range(Size::ZERO, ptr_size);
+    let with_ptr = |id: u64| {
+        let mut alloc: Allocation = Allocation::uninit(ptr_size, Align::ONE);
+        let ptr = Pointer::new(AllocId(NonZeroU64::new(id).unwrap()), Size::ZERO);
+        alloc.write_scalar(&cx, whole, Scalar::from_pointer(ptr, &cx)).unwrap();
+        alloc
+    };
+
+    // The bytes of the two pointers are the same, but they point to different allocations.
+    let (a, b) = (with_ptr(1), with_ptr(2));
+    assert!(a.range_bytes_eq(whole, &with_ptr(1), whole, &cx));
+    assert!(!a.range_bytes_eq(whole, &b, whole, &cx));
+
+    // Plain bytes equal to the bytes of a pointer have no provenance.
+    let mut plain: Allocation = Allocation::uninit(ptr_size, Align::ONE);
+    plain.write_scalar(&cx, whole, Scalar::from_uint(0u8, ptr_size)).unwrap();
+    assert!(!a.range_bytes_eq(whole, &plain, whole, &cx));
+}
+
+#[test]
+fn range_bytes_eq_compares_subranges_and_init() {
+    let cx = TargetDataLayout::default();
+    let one = Size::from_bytes(1);
+    let alloc: Allocation = Allocation::from_bytes_byte_aligned_immutable(&[1u8, 2, 3, 1, 2][..]);
+    let range = |start, size| alloc_range(Size::from_bytes(start), Size::from_bytes(size));
+    assert!(alloc.range_bytes_eq(range(0, 2), &alloc, range(3, 2), &cx));
+    assert!(!alloc.range_bytes_eq(range(0, 2), &alloc, range(1, 2), &cx));
+    assert!(!alloc.range_bytes_eq(range(0, 2), &alloc, range(3, 1), &cx));
+    assert!(!alloc.range_bytes_eq(range(0, 2), &alloc, range(4, 2), &cx));
+
+    // Uninitialized bytes are equal to each other, but not to initialized ones.
+    let mut a: Allocation = Allocation::uninit(Size::from_bytes(2), Align::ONE);
+    let b: Allocation = Allocation::uninit(Size::from_bytes(2), Align::ONE);
+    assert!(a.range_bytes_eq(range(0, 2), &b, range(0, 2), &cx));
+    a.write_scalar(&cx, alloc_range(one, one), Scalar::from_u8(0)).unwrap();
+    assert!(!a.range_bytes_eq(range(0, 2), &b, range(0, 2), &cx));
+    assert!(a.range_bytes_eq(range(0, 1), &b, range(0, 1), &cx));
+}
+
+#[test]
+fn range_content_hash() {
+    let cx = TargetDataLayout::default();
+    let range = |start, size| alloc_range(Size::from_bytes(start), Size::from_bytes(size));
+    let alloc: Allocation = Allocation::from_bytes_byte_aligned_immutable(&[1u8, 2, 3, 1, 2][..]);
+    let hash = |alloc: &Allocation, start, size| alloc.range_content_hash(range(start, size), &cx);
+
+    // The hash only depends on the contents of the range.
+    assert_eq!(hash(&alloc, 0, 2), hash(&alloc, 3, 2));
+    assert_ne!(hash(&alloc, 0, 2), hash(&alloc, 1, 2));
+    assert_ne!(hash(&alloc, 0, 0), hash(&alloc, 0, 1));
+    assert_eq!(hash(&alloc, 0, 0), hash(&alloc, 5, 0));
+    assert_eq!(hash(&alloc, 4, 2), None);
+
+    // Uninitialized bytes hash the same regardless of their value, but not like any initialized
+    // byte.
+    let mut a: Allocation = Allocation::uninit(Size::from_bytes(2), Align::ONE);
+    let b: Allocation = Allocation::uninit(Size::from_bytes(2), Align::ONE);
+    assert_eq!(hash(&a, 0, 2), hash(&b, 0, 2));
+    a.write_scalar(&cx, range(1, 1), Scalar::from_u8(0)).unwrap();
+    assert_ne!(hash(&a, 0, 2), hash(&b, 0, 2));
+    assert_eq!(hash(&a, 0, 1), hash(&b, 0, 1));
+
+    // Ranges overlapping a pointer have no content hash.
+    let ptr_size = cx.pointer_size;
+    let mut alloc: Allocation = Allocation::uninit(ptr_size + Size::from_bytes(1), Align::ONE);
+    let ptr = Pointer::new(AllocId(NonZeroU64::new(1).unwrap()), Size::ZERO);
+    alloc
+        .write_scalar(&cx, alloc_range(Size::ZERO, ptr_size), Scalar::from_pointer(ptr, &cx))
+        .unwrap();
+    assert_eq!(
+        alloc.range_content_hash(
+            alloc_range(ptr_size - Size::from_bytes(1), Size::from_bytes(1)),
+            &cx
+        ),
+        None
+    );
+    assert!(alloc.range_content_hash(alloc_range(ptr_size, Size::from_bytes(1)), &cx).is_some());
+}
+
+#[test]
+fn find_bytes_skips_uninit_and_pointers() {
+    let cx = TargetDataLayout::default();
+    let alloc: Allocation = Allocation::from_bytes_byte_aligned_immutable(&[1u8, 2, 3, 1, 2][..]);
+    assert_eq!(alloc.find_bytes(&[1, 2], &cx), Some(Size::ZERO));
+    assert_eq!(alloc.find_bytes(&[2, 3, 1], &cx), Some(Size::from_bytes(1)));
+    assert_eq!(alloc.find_bytes(&[2, 1], &cx), None);
+    assert_eq!(alloc.find_bytes(&[1, 2, 3, 1, 2, 3], &cx), None);
+    assert_eq!(alloc.find_bytes(&[], &cx), None);
+
+    // Uninitialized bytes never match, even though they are zero in memory.
+    let one = Size::from_bytes(1);
+    let mut alloc: Allocation = Allocation::uninit(Size::from_bytes(3), Align::ONE);
+    alloc.write_scalar(&cx, alloc_range(one * 2, one), Scalar::from_u8(0)).unwrap();
+    assert_eq!(alloc.find_bytes(&[0, 0], &cx), None);
+    assert_eq!(alloc.find_bytes(&[0], &cx), Some(one * 2));
+
+    // Neither do the bytes of pointers.
+    let ptr_size = cx.pointer_size;
+    let mut alloc: Allocation = Allocation::uninit(ptr_size + one, Align::ONE);
+    let ptr = Pointer::new(AllocId(NonZeroU64::new(1).unwrap()), Size::ZERO);
+    alloc
+        .write_scalar(&cx, alloc_range(Size::ZERO, ptr_size), Scalar::from_pointer(ptr, &cx))
+        .unwrap();
+    alloc.write_scalar(&cx, alloc_range(ptr_size, one), Scalar::from_u8(0)).unwrap();
+    assert_eq!(alloc.find_bytes(&[0], &cx), Some(ptr_size));
+}
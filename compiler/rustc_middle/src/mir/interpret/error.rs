@@ -1,8 +1,8 @@
-use super::{AllocId, AllocRange, ConstAlloc, Pointer, Scalar};
+use super::{AllocId, AllocRange, ConstAlloc, ConstShape, Pointer, Scalar};
 
 use crate::mir::interpret::ConstValue;
 use crate::query::TyCtxtAt;
-use crate::ty::{layout, tls, Ty, ValTree};
+use crate::ty::{layout, tls, ConstInt, Ty, ValTree};
 
 use rustc_data_structures::sync::Lock;
 use rustc_errors::{
@@ -73,6 +73,7 @@ fn into(self) -> ErrorGuaranteed {
 pub type EvalToAllocationRawResult<'tcx> = Result<ConstAlloc<'tcx>, ErrorHandled>;
 pub type EvalToConstValueResult<'tcx> = Result<ConstValue<'tcx>, ErrorHandled>;
 pub type EvalToValTreeResult<'tcx> = Result<Option<ValTree<'tcx>>, ErrorHandled>;
+pub type EvalToConstShapeResult = Result<ConstShape, ErrorHandled>;
 
 pub fn struct_error<'tcx>(
     tcx: TyCtxtAt<'tcx>,
@@ -270,10 +271,10 @@ pub enum UndefinedBehaviorInfo<'a> {
     Unreachable,
     /// A slice/array index projection went out-of-bounds.
     BoundsCheckFailed { len: u64, index: u64 },
-    /// Something was divided by 0 (x / 0).
-    DivisionByZero,
-    /// Something was "remainded" by 0 (x % 0).
-    RemainderByZero,
+    /// Something was divided by 0 (x / 0). Contains the dividend.
+    DivisionByZero(ConstInt),
+    /// Something was "remainded" by 0 (x % 0). Contains the dividend.
+    RemainderByZero(ConstInt),
     /// Signed division overflowed (INT_MIN / -1).
     DivisionOverflow,
     /// Signed remainder overflowed (INT_MIN % -1).
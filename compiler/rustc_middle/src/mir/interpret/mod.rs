@@ -126,7 +126,7 @@ macro_rules! throw_ub_custom {
 use std::sync::atomic::{AtomicU32, Ordering};
 
 use rustc_ast::LitKind;
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxIndexSet};
 use rustc_data_structures::sync::{HashMapExt, Lock};
 use rustc_data_structures::tiny_list::TinyList;
 use rustc_errors::ErrorGuaranteed;
@@ -142,14 +142,14 @@ macro_rules! throw_ub_custom {
 use crate::ty::{self, Instance, Ty, TyCtxt};
 
 pub use self::error::{
-    struct_error, CheckInAllocMsg, ErrorHandled, EvalToAllocationRawResult, EvalToConstValueResult,
-    EvalToValTreeResult, ExpectedKind, InterpError, InterpErrorInfo, InterpResult, InvalidMetaKind,
-    InvalidProgramInfo, MachineStopType, PointerKind, ReportedErrorInfo, ResourceExhaustionInfo,
-    ScalarSizeMismatch, UndefinedBehaviorInfo, UninitBytesAccess, UnsupportedOpInfo,
-    ValidationErrorInfo, ValidationErrorKind,
+    struct_error, CheckInAllocMsg, ErrorHandled, EvalToAllocationRawResult, EvalToConstShapeResult,
+    EvalToConstValueResult, EvalToValTreeResult, ExpectedKind, InterpError, InterpErrorInfo,
+    InterpResult, InvalidMetaKind, InvalidProgramInfo, MachineStopType, PointerKind,
+    ReportedErrorInfo, ResourceExhaustionInfo, ScalarSizeMismatch, UndefinedBehaviorInfo,
+    UninitBytesAccess, UnsupportedOpInfo, ValidationErrorInfo, ValidationErrorKind,
 };
 
-pub use self::value::{get_slice_bytes, ConstAlloc, ConstValue, Scalar};
+pub use self::value::{get_slice_bytes, ConstAlloc, ConstShape, ConstValue, Scalar};
 
 pub use self::allocation::{
     alloc_range, AllocBytes, AllocError, AllocRange, AllocResult, Allocation, ConstAllocation,
@@ -183,6 +183,29 @@ pub fn display(self, tcx: TyCtxt<'tcx>) -> String {
     }
 }
 
+/// The constants and statics evaluated so far, and which of them were evaluated while evaluating
+/// which. Only tracked with `-Z dump-const-deps`.
+#[derive(Default)]
+pub struct ConstEvalDeps<'tcx> {
+    /// The globals currently being evaluated, innermost last.
+    pub stack: Vec<GlobalId<'tcx>>,
+    /// Every global whose evaluation was started so far.
+    pub nodes: FxIndexSet<GlobalId<'tcx>>,
+    /// `(a, b)` if the evaluation of `b` was forced during the evaluation of `a`.
+    pub edges: FxIndexSet<(GlobalId<'tcx>, GlobalId<'tcx>)>,
+}
+
+impl<'tcx> ConstEvalDeps<'tcx> {
+    /// Records that the evaluation of `cid` was requested, by the innermost global currently being
+    /// evaluated if there is one.
+    pub fn record_forcing(&mut self, cid: GlobalId<'tcx>) {
+        if let Some(&parent) = self.stack.last() {
+            self.edges.insert((parent, cid));
+        }
+        self.nodes.insert(cid);
+    }
+}
+
 /// Input argument for `tcx.lit_to_const`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, HashStable)]
 pub struct LitToConstInput<'tcx> {
@@ -1,12 +1,15 @@
 use super::{ErrorHandled, EvalToConstValueResult, EvalToValTreeResult, GlobalId};
 
+use crate::error::UnresolvedConst;
 use crate::mir;
 use crate::query::{TyCtxtAt, TyCtxtEnsure};
+use crate::traits::Reveal;
 use crate::ty::subst::InternalSubsts;
 use crate::ty::visit::TypeVisitableExt;
 use crate::ty::{self, TyCtxt};
 use rustc_hir::def::DefKind;
 use rustc_hir::def_id::DefId;
+use rustc_query_system::query::QueryCache;
 use rustc_session::lint;
 use rustc_span::{Span, DUMMY_SP};
 
@@ -61,7 +64,7 @@ pub fn const_eval_resolve(
                 let cid = GlobalId { instance, promoted: ct.promoted };
                 self.const_eval_global_id(param_env, cid, span)
             }
-            Ok(None) => Err(ErrorHandled::TooGeneric),
+            Ok(None) => Err(self.unresolved_const(param_env, ct.def, ct.substs, span)),
             Err(err) => Err(ErrorHandled::Reported(err.into())),
         }
     }
@@ -117,11 +120,45 @@ pub fn const_eval_resolve_for_typeck(
                     }
                 })
             }
-            Ok(None) => Err(ErrorHandled::TooGeneric),
+            Ok(None) => Err(self.unresolved_const(param_env, ct.def, ct.substs, span)),
             Err(err) => Err(ErrorHandled::Reported(err.into())),
         }
     }
 
+    /// Called when `def` can't be resolved to an instance with `substs`. This usually means that
+    /// the constant is too generic. If `substs` are fully monomorphic the trait solver had all the
+    /// information it needs, so unless the constant is a `default` item that may still be
+    /// specialized, no impl providing it applies and we report that instead of pretending that
+    /// evaluation may succeed later.
+    fn unresolved_const(
+        self,
+        param_env: ty::ParamEnv<'tcx>,
+        def: DefId,
+        substs: ty::SubstsRef<'tcx>,
+        span: Option<Span>,
+    ) -> ErrorHandled {
+        if substs.has_non_region_param() {
+            return ErrorHandled::TooGeneric;
+        }
+        // `default` items are only resolved once all impls are revealed.
+        if param_env.reveal() == Reveal::UserFacing
+            && let Ok(Some(_)) =
+                ty::Instance::resolve(self, param_env.with_reveal_all_normalized(self), def, substs)
+        {
+            return ErrorHandled::TooGeneric;
+        }
+        let span = span.unwrap_or_else(|| self.def_span(def));
+        let reported = if substs.references_error() {
+            self.sess.delay_span_bug(span, "unresolved constant with erroneous substs")
+        } else {
+            self.sess.emit_err(UnresolvedConst {
+                span,
+                name: self.def_path_str_with_substs(def, substs),
+            })
+        };
+        ErrorHandled::Reported(reported.into())
+    }
+
     pub fn const_eval_instance(
         self,
         param_env: ty::ParamEnv<'tcx>,
@@ -143,6 +180,7 @@ pub fn const_eval_global_id(
         // Const-eval shouldn't depend on lifetimes at all, so we can erase them, which should
         // improve caching of queries.
         let inputs = self.erase_regions(param_env.and(cid));
+        self.note_eval_to_const_value_raw(inputs);
         if let Some(span) = span {
             self.at(span).eval_to_const_value_raw(inputs)
         } else {
@@ -164,6 +202,7 @@ pub fn const_eval_global_id_for_typeck(
         // improve caching of queries.
         let inputs = self.erase_regions(param_env.and(cid));
         debug!(?inputs);
+        self.note_eval_to_valtree(inputs);
         if let Some(span) = span {
             self.at(span).eval_to_valtree(inputs)
         } else {
@@ -179,6 +218,44 @@ pub fn eval_static_initializer(
     ) -> Result<mir::ConstAllocation<'tcx>, ErrorHandled> {
         self.at(DUMMY_SP).eval_static_initializer(def_id)
     }
+
+    /// Called before the `eval_to_allocation_raw` query is invoked for a constant, by everything
+    /// that invokes it, including the interpreter when one constant uses another. With
+    /// `-Z trace-const-forcing` this prints the request, and with `-Z dump-const-deps` it records
+    /// that the global currently being evaluated, if any, depends on the constant.
+    pub fn note_eval_to_allocation_raw(self, key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) {
+        self.note_const_forcing("eval_to_allocation_raw", key.value, || {
+            self.query_system.caches.eval_to_allocation_raw.lookup(&key).is_some()
+        });
+    }
+
+    /// Like `note_eval_to_allocation_raw`, for the `eval_to_const_value_raw` query.
+    pub fn note_eval_to_const_value_raw(self, key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) {
+        self.note_const_forcing("eval_to_const_value_raw", key.value, || {
+            self.query_system.caches.eval_to_const_value_raw.lookup(&key).is_some()
+        });
+    }
+
+    /// Like `note_eval_to_allocation_raw`, for the `eval_to_valtree` query.
+    pub fn note_eval_to_valtree(self, key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) {
+        self.note_const_forcing("eval_to_valtree", key.value, || {
+            self.query_system.caches.eval_to_valtree.lookup(&key).is_some()
+        });
+    }
+
+    /// Handles a request to evaluate `cid` through `query`. `cached` is only called when tracing,
+    /// and tells whether the query already has a result for `cid`.
+    fn note_const_forcing(self, query: &str, cid: GlobalId<'tcx>, cached: impl FnOnce() -> bool) {
+        if self.sess.opts.unstable_opts.trace_const_forcing {
+            let status = if cached() { "cached" } else { "evaluating" };
+            eprintln!("const-forcing: {query}({}) {status}", cid.display(self));
+        }
+        // This is done here rather than when the query runs, so that constants whose value is
+        // already cached still show up as dependencies.
+        if self.sess.opts.unstable_opts.dump_const_deps.is_some() {
+            self.const_eval_deps.lock().record_forcing(cid);
+        }
+    }
 }
 
 impl<'tcx> TyCtxtAt<'tcx> {
@@ -206,6 +283,7 @@ fn eval_to_allocation(
     ) -> Result<mir::ConstAllocation<'tcx>, ErrorHandled> {
         let param_env = param_env.with_const();
         trace!("eval_to_allocation: Need to compute {:?}", gid);
+        self.tcx.note_eval_to_allocation_raw(param_env.and(gid));
         let raw_const = self.eval_to_allocation_raw(param_env.and(gid))?;
         Ok(self.global_alloc(raw_const.alloc_id).unwrap_memory())
     }
@@ -229,6 +307,7 @@ pub fn const_eval_poly(self, def_id: DefId) {
         // Const-eval shouldn't depend on lifetimes at all, so we can erase them, which should
         // improve caching of queries.
         let inputs = self.tcx.erase_regions(param_env.and(cid));
+        self.tcx.note_eval_to_const_value_raw(inputs);
         self.eval_to_const_value_raw(inputs)
     }
 
@@ -240,6 +319,7 @@ pub fn eval_static_initializer(self, def_id: DefId) {
         let gid = GlobalId { instance, promoted: None };
         let param_env = ty::ParamEnv::reveal_all().with_const();
         trace!("eval_to_allocation: Need to compute {:?}", gid);
+        self.tcx.note_eval_to_allocation_raw(param_env.and(gid));
         self.eval_to_allocation_raw(param_env.and(gid))
     }
 }
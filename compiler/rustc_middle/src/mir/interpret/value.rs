@@ -9,7 +9,7 @@
 use rustc_macros::HashStable;
 use rustc_target::abi::{HasDataLayout, Size};
 
-use crate::ty::{ParamEnv, ScalarInt, Ty, TyCtxt};
+use crate::ty::{self, ParamEnv, ScalarInt, Ty, TyCtxt};
 
 use super::{
     AllocId, AllocRange, ConstAllocation, InterpResult, Pointer, PointerArithmetic, Provenance,
@@ -25,6 +25,35 @@ pub struct ConstAlloc<'tcx> {
     pub ty: Ty<'tcx>,
 }
 
+/// The coarse shape of a constant, as computed by the `const_shape` query.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, HashStable)]
+pub enum ConstShape {
+    /// An integer, float, `bool` or `char`.
+    Scalar,
+    /// A reference, raw pointer or function pointer.
+    Pointer,
+    /// An array.
+    Array,
+    /// A struct, enum, union, tuple or closure.
+    Aggregate,
+    /// Anything else, like a function item.
+    Other,
+}
+
+impl ConstShape {
+    pub fn from_ty(ty: Ty<'_>) -> Self {
+        match ty.kind() {
+            ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) | ty::Float(_) => ConstShape::Scalar,
+            ty::Ref(..) | ty::RawPtr(_) | ty::FnPtr(_) => ConstShape::Pointer,
+            ty::Array(..) => ConstShape::Array,
+            ty::Adt(..) | ty::Tuple(_) | ty::Closure(..) | ty::Generator(..) => {
+                ConstShape::Aggregate
+            }
+            _ => ConstShape::Other,
+        }
+    }
+}
+
 /// Represents a constant value in Rust. `Scalar` and `Slice` are optimizations for
 /// array length computations, enum discriminants and the pattern matching logic.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, TyEncodable, TyDecodable, Hash)]
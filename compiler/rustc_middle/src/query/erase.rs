@@ -150,10 +150,22 @@ impl<T> EraseType for Option<&'_ [T]> {
     type Result = [u8; size_of::<Option<&'static [()]>>()];
 }
 
+impl EraseType for Option<&'_ str> {
+    type Result = [u8; size_of::<Option<&'static str>>()];
+}
+
 impl EraseType for Option<rustc_middle::hir::Owner<'_>> {
     type Result = [u8; size_of::<Option<rustc_middle::hir::Owner<'static>>>()];
 }
 
+impl EraseType for Option<ty::ValTree<'_>> {
+    type Result = [u8; size_of::<Option<ty::ValTree<'static>>>()];
+}
+
+impl EraseType for Option<(ty::ValTree<'_>, usize)> {
+    type Result = [u8; size_of::<Option<(ty::ValTree<'static>, usize)>>()];
+}
+
 impl EraseType for Option<mir::DestructuredConstant<'_>> {
     type Result = [u8; size_of::<Option<mir::DestructuredConstant<'static>>>()];
 }
@@ -162,6 +174,10 @@ impl EraseType for Option<ty::EarlyBinder<ty::TraitRef<'_>>> {
     type Result = [u8; size_of::<Option<ty::EarlyBinder<ty::TraitRef<'static>>>>()];
 }
 
+impl EraseType for Option<Ty<'_>> {
+    type Result = [u8; size_of::<Option<Ty<'static>>>()];
+}
+
 impl EraseType for Option<ty::EarlyBinder<Ty<'_>>> {
     type Result = [u8; size_of::<Option<ty::EarlyBinder<Ty<'static>>>>()];
 }
@@ -219,10 +235,13 @@ impl EraseType for $ty {
     Option<rustc_span::def_id::DefId>,
     Option<rustc_span::def_id::LocalDefId>,
     Option<rustc_span::Span>,
+    Option<rustc_target::abi::Align>,
     Option<rustc_target::spec::PanicStrategy>,
+    Option<u128>,
     Option<usize>,
     Result<(), rustc_errors::ErrorGuaranteed>,
     Result<(), rustc_middle::traits::query::NoSolution>,
+    Result<rustc_middle::mir::interpret::ConstShape, rustc_middle::mir::interpret::ErrorHandled>,
     Result<rustc_middle::traits::EvaluationResult, rustc_middle::traits::OverflowError>,
     rustc_ast::expand::allocator::AllocatorKind,
     rustc_attr::ConstStability,
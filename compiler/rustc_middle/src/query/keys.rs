@@ -518,6 +518,14 @@ fn default_span(&self, _: TyCtxt<'_>) -> Span {
     }
 }
 
+impl<'tcx> Key for (Ty<'tcx>, mir::interpret::ConstAllocation<'tcx>) {
+    type CacheSelector = DefaultCacheSelector<Self>;
+
+    fn default_span(&self, _: TyCtxt<'_>) -> Span {
+        DUMMY_SP
+    }
+}
+
 impl Key for HirId {
     type CacheSelector = DefaultCacheSelector<Self>;
 
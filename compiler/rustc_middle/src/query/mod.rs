@@ -21,7 +21,8 @@
 use crate::mir;
 use crate::mir::interpret::GlobalId;
 use crate::mir::interpret::{
-    ConstValue, EvalToAllocationRawResult, EvalToConstValueResult, EvalToValTreeResult,
+    ConstValue, EvalToAllocationRawResult, EvalToConstShapeResult, EvalToConstValueResult,
+    EvalToValTreeResult,
 };
 use crate::mir::interpret::{LitToConstError, LitToConstInput};
 use crate::mir::mono::CodegenUnit;
@@ -1067,6 +1068,46 @@
         cache_on_disk_if { true }
     }
 
+    /// Classifies a constant as a scalar, pointer, array or aggregate. This only looks at the type
+    /// of the constant and doesn't evaluate it, so it is cheap even for large or slow constants,
+    /// but it doesn't report evaluation errors either.
+    query const_shape(key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) -> EvalToConstShapeResult {
+        desc { |tcx|
+            "classifying the shape of `{}`",
+            key.value.display(tcx)
+        }
+    }
+
+    /// Evaluates an integer constant and returns the narrowest integer type with the same
+    /// signedness that can hold its value, e.g. `u8` for `const X: u32 = 255;`. Returns `None` for
+    /// constants of other types and if evaluation fails.
+    query const_minimal_int_ty(key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) -> Option<Ty<'tcx>> {
+        desc { |tcx|
+            "computing the narrowest integer type for the value of `{}`",
+            key.value.display(tcx)
+        }
+    }
+
+    /// Evaluates a constant and renders its value the way diagnostics do, e.g. `Foo { x: 1_u8 }`
+    /// or `&[1_i32, 2_i32]`, using the printer of type-level constants where possible and the one
+    /// of MIR constants otherwise. Returns `None` if the constant fails to evaluate.
+    query const_eval_pretty(key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) -> Option<&'tcx str> {
+        desc { |tcx|
+            "pretty-printing the value of `{}`",
+            key.value.display(tcx)
+        }
+    }
+
+    /// Evaluates a constant and returns the alignment of the allocation holding its value, which
+    /// is at least the ABI alignment of its type, e.g. 16 for a `#[repr(align(16))]` struct.
+    /// Returns `None` if evaluation fails.
+    query const_allocation_align(key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) -> Option<abi::Align> {
+        desc { |tcx|
+            "computing the alignment of the value of `{}`",
+            key.value.display(tcx)
+        }
+    }
+
     /// Evaluate a constant and convert it to a type level constant or
     /// return `None` if that is not possible.
     query eval_to_valtree(
@@ -1075,11 +1116,84 @@
         desc { "evaluating type-level constant" }
     }
 
+    /// Evaluates a constant again and reports whether it computed with values whose size or byte
+    /// order depend on the target, such as `usize` values or transmuted bytes. This is only a
+    /// heuristic: a `true` result means the value *may* differ between targets. Returns `false`
+    /// if the constant fails to evaluate.
+    query const_is_target_dependent(key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) -> bool {
+        desc { |tcx|
+            "checking whether `{}` depends on the target",
+            key.value.display(tcx)
+        }
+    }
+
+    /// Evaluates a constant again and returns the names of the intrinsics it called, directly or
+    /// through the functions it called, in the order of their first call. This shows which
+    /// intrinsics real constants rely on, e.g. when considering their stabilization for `const`
+    /// use. Returns an empty set if the constant fails to evaluate.
+    query const_used_intrinsics(key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) -> &'tcx FxIndexSet<Symbol> {
+        desc { |tcx|
+            "collecting the intrinsics used by `{}`",
+            key.value.display(tcx)
+        }
+    }
+
+    /// Evaluates a constant and checks that its final value, including everything it points to,
+    /// is valid for its type, e.g. that it contains no invalid `bool`s or dangling references.
+    /// Evaluation already does this and reports invalid values as errors, so this is only meant
+    /// for tools that want to ask directly. Returns `false` if the constant fails to evaluate.
+    query const_is_valid(key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) -> bool {
+        desc { |tcx|
+            "checking whether the value of `{}` is valid",
+            key.value.display(tcx)
+        }
+    }
+
+    /// Returns the statics whose addresses are stored in the final value of a constant, either
+    /// directly or in memory the value points to. Their contents are not looked at, as constants
+    /// may point to statics without reading them. Returns an empty list if the constant fails to
+    /// evaluate.
+    query const_referenced_statics(key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>) -> &'tcx [DefId] {
+        desc { |tcx|
+            "collecting the statics referenced by `{}`",
+            key.value.display(tcx)
+        }
+    }
+
+    /// Converts the final value of a constant, given by its type and its allocation, to a type
+    /// level constant, and returns it with its number of nodes. Equal values are interned to the
+    /// same allocation, so the valtree is only built once for all constants with an equal value.
+    /// Returns `None` if the value has no valtree, leaving it to the caller to report why.
+    query shared_valtree(
+        key: (Ty<'tcx>, mir::interpret::ConstAllocation<'tcx>)
+    ) -> Option<(ty::ValTree<'tcx>, usize)> {
+        desc { "converting a constant value to a type-level constant" }
+    }
+
+    /// Returns the values of all constants that were evaluated to a valtree in this compilation
+    /// session, in the order they were evaluated. This is only filled with
+    /// `-Z collect-const-values`, and is meant to be used by tools at the end of compilation, as
+    /// the result is computed once and later evaluations are not included in it. With incremental
+    /// compilation, constants whose value was loaded from the cache are missing.
+    query collected_const_values(_: ()) -> &'tcx [(GlobalId<'tcx>, Ty<'tcx>, ty::ValTree<'tcx>)] {
+        eval_always
+        no_hash
+        desc { "collecting the values of evaluated constants" }
+    }
+
     /// Converts a type level constant value into `ConstValue`
     query valtree_to_const_val(key: (Ty<'tcx>, ty::ValTree<'tcx>)) -> ConstValue<'tcx> {
         desc { "converting type-level constant value to mir constant value"}
     }
 
+    /// Converts an evaluated `mir::ConstantKind` into a type level constant value. Returns
+    /// `None` if the value has no valtree representation.
+    query const_val_to_valtree(
+        key: ty::ParamEnvAnd<'tcx, mir::ConstantKind<'tcx>>
+    ) -> Option<ty::ValTree<'tcx>> {
+        desc { "converting mir constant value to type-level constant value"}
+    }
+
     /// Destructures array, ADT or tuple constants into the constants
     /// of their fields.
     query destructure_const(key: ty::Const<'tcx>) -> ty::DestructuredConst<'tcx> {
@@ -1094,6 +1208,14 @@
         desc { "destructuring MIR constant"}
     }
 
+    /// Tries to read the discriminant of a `mir::ConstantKind` enum without reading any of its
+    /// fields. The discriminant is returned as raw bits.
+    query try_read_mir_constant_discriminant(
+        key: ty::ParamEnvAnd<'tcx, mir::ConstantKind<'tcx>>
+    ) -> Option<u128> {
+        desc { "reading the discriminant of MIR constant"}
+    }
+
     query const_caller_location(key: (rustc_span::Symbol, u32, u32)) -> ConstValue<'tcx> {
         desc { "getting a &core::panic::Location referring to a span" }
     }
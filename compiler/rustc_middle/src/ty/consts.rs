@@ -1,10 +1,14 @@
 use crate::middle::resolve_bound_vars as rbv;
-use crate::mir::interpret::LitToConstInput;
+use crate::mir;
+use crate::mir::interpret::{
+    alloc_range, write_target_uint, AllocId, AllocRange, Allocation, ConstAllocation, ConstValue,
+    LitToConstInput, Pointer, Scalar,
+};
 use crate::ty::{self, InternalSubsts, ParamEnv, ParamEnvAnd, Ty, TyCtxt};
 use rustc_data_structures::intern::Interned;
 use rustc_error_messages::MultiSpan;
 use rustc_hir as hir;
-use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def::{CtorKind, DefKind, Res};
 use rustc_hir::def_id::LocalDefId;
 use rustc_macros::HashStable;
 
@@ -14,7 +18,10 @@
 
 pub use int::*;
 pub use kind::*;
-use rustc_span::DUMMY_SP;
+use rustc_span::{Symbol, DUMMY_SP};
+use rustc_target::abi::{Align, Endian, Size, VariantIdx};
+use std::cmp::Ordering;
+use std::iter;
 pub use valtree::*;
 
 /// Use this rather than `ConstData`, whenever possible.
@@ -32,6 +39,31 @@ pub struct ConstData<'tcx> {
 #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
 static_assert_size!(ConstData<'_>, 40);
 
+/// An evaluated constant that is hashed and compared by its value instead of by the identity of
+/// its interned `ConstData`. Created by `Const::as_value_key`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ConstValueKey<'tcx> {
+    ty: Ty<'tcx>,
+    valtree: ty::ValTree<'tcx>,
+}
+
+impl<'tcx> ConstValueKey<'tcx> {
+    #[inline]
+    pub fn ty(self) -> Ty<'tcx> {
+        self.ty
+    }
+
+    #[inline]
+    pub fn valtree(self) -> ty::ValTree<'tcx> {
+        self.valtree
+    }
+
+    #[inline]
+    pub fn to_const(self, tcx: TyCtxt<'tcx>) -> Const<'tcx> {
+        Const::new_value(tcx, self.valtree, self.ty)
+    }
+}
+
 impl<'tcx> Const<'tcx> {
     #[inline]
     pub fn ty(self) -> Ty<'tcx> {
@@ -240,6 +272,17 @@ fn try_eval_lit_or_param(
         }
     }
 
+    /// Returns a key that identifies this constant by its value, or `None` if the constant isn't
+    /// evaluated yet. Equal values always produce equal keys, no matter how the constants were
+    /// created.
+    #[inline]
+    pub fn as_value_key(self) -> Option<ConstValueKey<'tcx>> {
+        match self.kind() {
+            ty::ConstKind::Value(valtree) => Some(ConstValueKey { ty: self.ty(), valtree }),
+            _ => None,
+        }
+    }
+
     /// Panics if self.kind != ty::ConstKind::Value
     pub fn to_valtree(self) -> ty::ValTree<'tcx> {
         match self.kind() {
@@ -280,6 +323,19 @@ pub fn from_target_usize(tcx: TyCtxt<'tcx>, n: u64) -> Self {
         Self::from_bits(tcx, n as u128, ParamEnv::empty().and(tcx.types.usize))
     }
 
+    /// Creates an interned constant from an evaluated `ConstValue`, the inverse of
+    /// `valtree_to_const_val`. Returns `None` if the value can't be represented at the type
+    /// level, e.g. because it contains raw pointers or trait objects.
+    pub fn from_const_value(
+        tcx: TyCtxt<'tcx>,
+        val: ConstValue<'tcx>,
+        ty: Ty<'tcx>,
+    ) -> Option<Self> {
+        let key = ParamEnv::reveal_all().and(mir::ConstantKind::Val(val, ty));
+        let valtree = tcx.const_val_to_valtree(key)?;
+        Some(ty::Const::new_value(tcx, valtree, ty))
+    }
+
     #[inline]
     /// Attempts to evaluate the given constant to bits. Can fail to evaluate in the presence of
     /// generics (or erroneous code) or if the value can't be represented as bits (e.g. because it
@@ -296,11 +352,611 @@ pub fn try_eval_bits(
         self.kind().eval(tcx, param_env).try_to_bits(size)
     }
 
+    /// Evaluates an integer constant and returns whether all bits set in `mask` are also set in
+    /// it, like `flags.contains(mask)` for a bitflags type. Returns `None` if the constant isn't an
+    /// integer or can't be evaluated.
+    pub fn contains_bits(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        mask: u128,
+    ) -> Option<bool> {
+        if !self.ty().is_integral() {
+            return None;
+        }
+        Some(self.kind().eval(tcx, param_env).try_to_scalar_int()?.contains_bits(mask))
+    }
+
+    #[inline]
+    /// Like `try_eval_bits`, but sign-extends the value according to the size of `ty`, which
+    /// must be a signed integer type.
+    pub fn try_eval_i128(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        ty: Ty<'tcx>,
+    ) -> Option<i128> {
+        assert_eq!(self.ty(), ty);
+        let size = tcx.layout_of(param_env.with_reveal_all_normalized(tcx).and(ty)).ok()?.size;
+        self.kind().eval(tcx, param_env).try_to_scalar_int()?.try_to_int(size).ok()
+    }
+
+    #[inline]
+    /// Like `try_eval_i128`, but also returns `None` if the value does not fit into an `i64`.
+    pub fn try_eval_i64(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        ty: Ty<'tcx>,
+    ) -> Option<i64> {
+        self.try_eval_i128(tcx, param_env, ty)?.try_into().ok()
+    }
+
+    /// Evaluates two integer constants of the same type and compares them, taking the signedness
+    /// of the type into account. Returns `None` if the types differ, aren't integers, or one of
+    /// the constants can't be evaluated.
+    pub fn try_cmp(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Ordering> {
+        let ty = self.ty();
+        if ty != other.ty() {
+            return None;
+        }
+        match ty.kind() {
+            ty::Int(_) => Some(
+                self.try_eval_i128(tcx, param_env, ty)?
+                    .cmp(&other.try_eval_i128(tcx, param_env, ty)?),
+            ),
+            ty::Uint(_) => Some(
+                self.try_eval_bits(tcx, param_env, ty)?
+                    .cmp(&other.try_eval_bits(tcx, param_env, ty)?),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Evaluates two integer constants of the same type and adds them, wrapping around on
+    /// overflow. Returns the result and whether it overflowed, like `CheckedBinaryOp` does.
+    /// Returns `None` if the types differ, aren't integers, or one of the constants can't be
+    /// evaluated.
+    pub fn try_checked_add(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<(Const<'tcx>, bool)> {
+        self.try_checked_op(mir::BinOp::Add, other, tcx, param_env)
+    }
+
+    /// Like `try_checked_add`, but subtracts `other` from `self`.
+    pub fn try_checked_sub(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<(Const<'tcx>, bool)> {
+        self.try_checked_op(mir::BinOp::Sub, other, tcx, param_env)
+    }
+
+    /// Like `try_checked_add`, but multiplies the constants.
+    pub fn try_checked_mul(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<(Const<'tcx>, bool)> {
+        self.try_checked_op(mir::BinOp::Mul, other, tcx, param_env)
+    }
+
+    /// Evaluates two integer constants of the same type and adds them, wrapping around at the
+    /// boundary of the type like `i32::wrapping_add`. Returns `None` if the types differ, aren't
+    /// integers, or one of the constants can't be evaluated.
+    pub fn try_wrapping_add(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        Some(self.try_checked_add(other, tcx, param_env)?.0)
+    }
+
+    /// Like `try_wrapping_add`, but subtracts `other` from `self`.
+    pub fn try_wrapping_sub(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        Some(self.try_checked_sub(other, tcx, param_env)?.0)
+    }
+
+    /// Like `try_wrapping_add`, but multiplies the constants.
+    pub fn try_wrapping_mul(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        Some(self.try_checked_mul(other, tcx, param_env)?.0)
+    }
+
+    /// Evaluates an integer constant and negates it, wrapping around at the boundary of its type
+    /// like `i32::wrapping_neg`. Returns `None` if the constant isn't an integer or can't be
+    /// evaluated.
+    pub fn try_wrapping_neg(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        let ty = self.ty();
+        if !ty.is_integral() {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(int.wrapping_neg()), ty))
+    }
+
+    /// Evaluates a constant of an unsigned integer type and returns the smallest power of two
+    /// greater than or equal to it, like `usize::next_power_of_two`. If that power is too large
+    /// for the type, the result is zero, which is what the library returns without overflow
+    /// checks, see `ScalarInt::next_power_of_two`. Returns `None` if the constant isn't an
+    /// unsigned integer or can't be evaluated.
+    pub fn try_next_power_of_two(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        let ty = self.ty();
+        if !matches!(ty.kind(), ty::Uint(_)) {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(int.next_power_of_two()), ty))
+    }
+
+    fn try_checked_op(
+        self,
+        op: mir::BinOp,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<(Const<'tcx>, bool)> {
+        let ty = self.ty();
+        if ty != other.ty() || !ty.is_integral() {
+            return None;
+        }
+        let l = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        let r = other.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        let (res, overflow) = l.overflowing_int_op(op, r, ty.is_signed())?;
+        Some((ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(res), ty), overflow))
+    }
+
+    /// Evaluates two integer or float constants of the same type and returns the larger one.
+    /// Integers are compared according to the signedness of their type. Floats follow IEEE
+    /// maxNum like `f32::max`: a NaN is only returned if both constants are NaN, and `self` is
+    /// returned if they compare equal, e.g. for `0.0` and `-0.0`. Returns `None` if the types
+    /// differ, aren't numbers, or one of the constants can't be evaluated.
+    pub fn try_max(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        self.try_min_max(other, true, tcx, param_env)
+    }
+
+    /// Like `try_max`, but returns the smaller constant, following IEEE minNum for floats.
+    pub fn try_min(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        self.try_min_max(other, false, tcx, param_env)
+    }
+
+    /// Evaluates a constant array of integers or floats and adds up its elements, like
+    /// `iter().sum()`. Unlike `sum`, this wraps around on integer overflow instead of panicking.
+    /// Floats are added in order starting at `0.0`, so the result is exactly what `sum` computes.
+    /// Returns the sum as a constant of the element type, or `None` if the constant isn't an array
+    /// of numbers or can't be evaluated.
+    pub fn try_array_sum(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        let ty::Array(elem_ty, _) = *self.ty().kind() else {
+            return None;
+        };
+        if !(elem_ty.is_integral() || elem_ty.is_floating_point()) {
+            return None;
+        }
+        let size = tcx.layout_of(param_env.with_reveal_all_normalized(tcx).and(elem_ty)).ok()?.size;
+        let ty::ValTree::Branch(elems) = self.kind().eval(tcx, param_env).try_to_value()? else {
+            return None;
+        };
+        let elems =
+            elems.iter().map(|elem| elem.try_to_scalar_int()).collect::<Option<Vec<_>>>()?;
+        let sum = ScalarInt::sum(elems, size, elem_ty.is_floating_point())?;
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(sum), elem_ty))
+    }
+
+    /// Evaluates three integer or float constants of the same type and restricts `self` to the
+    /// range `lo..=hi`, like `i32::clamp` and `f32::clamp`. Integers are compared according to
+    /// the signedness of their type. A NaN `self` is returned unchanged. Returns `None` where
+    /// `clamp` panics, i.e. if `lo > hi` or a float bound is NaN. Also returns `None` if the types
+    /// differ, aren't numbers, or one of the constants can't be evaluated.
+    pub fn try_clamp(
+        self,
+        lo: Const<'tcx>,
+        hi: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        let ty = self.ty();
+        if ty != lo.ty() || ty != hi.ty() {
+            return None;
+        }
+        let x = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        let lo = lo.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        let hi = hi.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        let res = match ty.kind() {
+            ty::Int(_) | ty::Uint(_) => x.clamp_int(lo, hi, ty.is_signed())?,
+            ty::Float(_) => x.float_clamp(lo, hi)?,
+            _ => return None,
+        };
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(res), ty))
+    }
+
+    fn try_min_max(
+        self,
+        other: Const<'tcx>,
+        max: bool,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        let ty = self.ty();
+        if ty != other.ty() {
+            return None;
+        }
+        let l = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        let r = other.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        let res = match ty.kind() {
+            ty::Int(_) | ty::Uint(_) => {
+                let ord = l.cmp_int(r, ty.is_signed())?;
+                if (max && ord.is_lt()) || (!max && ord.is_gt()) { r } else { l }
+            }
+            ty::Float(_) if max => l.float_max(r)?,
+            ty::Float(_) => l.float_min(r)?,
+            _ => return None,
+        };
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(res), ty))
+    }
+
+    /// Evaluates an integer constant and returns the number of ones in its binary representation
+    /// as a `u32` constant, like `u32::count_ones`. Returns `None` if the constant isn't an
+    /// integer or can't be evaluated.
+    pub fn try_count_ones(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        self.try_eval_bit_count(tcx, param_env, ScalarInt::count_ones)
+    }
+
+    /// Like `try_count_ones`, but counts the zeros within the size of the type.
+    pub fn try_count_zeros(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        self.try_eval_bit_count(tcx, param_env, ScalarInt::count_zeros)
+    }
+
+    /// Like `try_count_ones`, but counts the leading zeros. Only the bits within the size of the
+    /// type are counted, so this is 8 for `0u8`.
+    pub fn try_leading_zeros(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        self.try_eval_bit_count(tcx, param_env, ScalarInt::leading_zeros)
+    }
+
+    /// Like `try_count_ones`, but counts the trailing zeros. This is the number of bits of the
+    /// type if the constant is zero.
+    pub fn try_trailing_zeros(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        self.try_eval_bit_count(tcx, param_env, ScalarInt::trailing_zeros)
+    }
+
+    fn try_eval_bit_count(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        count: impl FnOnce(ScalarInt) -> u32,
+    ) -> Option<Const<'tcx>> {
+        if !self.ty().is_integral() {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        let n = ScalarInt::from(count(int));
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(n), tcx.types.u32))
+    }
+
+    /// Evaluates an integer constant and rotates its bits to the left by `n`, like
+    /// `u32::rotate_left`. The rotation happens within the size of the type and `n` is taken
+    /// modulo its number of bits. Returns `None` if the constant isn't an integer or can't be
+    /// evaluated.
+    pub fn try_rotate_left(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        n: u32,
+    ) -> Option<Const<'tcx>> {
+        if !self.ty().is_integral() {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(int.rotate_left(n)), self.ty()))
+    }
+
+    /// Like `try_rotate_left`, but rotates to the right.
+    pub fn try_rotate_right(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        n: u32,
+    ) -> Option<Const<'tcx>> {
+        if !self.ty().is_integral() {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        Some(ty::Const::new_value(
+            tcx,
+            ty::ValTree::from_scalar_int(int.rotate_right(n)),
+            self.ty(),
+        ))
+    }
+
+    /// Evaluates an integer constant and raises it to the power of `exp`. This wraps around on
+    /// overflow like `wrapping_pow`, which is what `pow` does with overflow checks disabled; it
+    /// doesn't report overflow like `pow` does in debug builds. Returns `None` if the constant
+    /// isn't an integer or can't be evaluated.
+    pub fn try_pow(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        exp: u32,
+    ) -> Option<Const<'tcx>> {
+        if !self.ty().is_integral() {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        Some(ty::Const::new_value(
+            tcx,
+            ty::ValTree::from_scalar_int(int.wrapping_pow(exp)),
+            self.ty(),
+        ))
+    }
+
+    /// Evaluates an integer constant and reverses the order of its bytes, like `u32::swap_bytes`.
+    /// Returns `None` if the constant isn't an integer or can't be evaluated.
+    pub fn try_swap_bytes(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        if !self.ty().is_integral() {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(int.swap_bytes()), self.ty()))
+    }
+
+    /// Evaluates an integer constant and reverses the order of its bits, like `u32::reverse_bits`.
+    /// Only the bits within the size of the type are reversed, so `1u8` becomes `0x80`. Returns
+    /// `None` if the constant isn't an integer or can't be evaluated.
+    pub fn try_reverse_bits(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        if !self.ty().is_integral() {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(int.reverse_bits()), self.ty()))
+    }
+
+    /// Evaluates a `u8` or `char` constant and applies `to_ascii_uppercase` to it. Non-ASCII values
+    /// are returned unchanged. Returns `None` for other types or if the constant can't be
+    /// evaluated.
+    pub fn try_ascii_uppercase(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        self.try_map_ascii(tcx, param_env, ScalarInt::to_ascii_uppercase)
+    }
+
+    /// Evaluates a `u8` or `char` constant and applies `to_ascii_lowercase` to it. Non-ASCII values
+    /// are returned unchanged. Returns `None` for other types or if the constant can't be
+    /// evaluated.
+    pub fn try_ascii_lowercase(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        self.try_map_ascii(tcx, param_env, ScalarInt::to_ascii_lowercase)
+    }
+
+    fn try_map_ascii(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        f: impl FnOnce(ScalarInt) -> ScalarInt,
+    ) -> Option<Const<'tcx>> {
+        if !matches!(self.ty().kind(), ty::Uint(ty::UintTy::U8) | ty::Char) {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(f(int)), self.ty()))
+    }
+
+    /// Like `u32::to_be`, for integer constants: swaps the bytes if the target is little endian
+    /// and only evaluates the constant otherwise.
+    pub fn try_to_be(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<Const<'tcx>> {
+        self.try_swap_bytes_unless(Endian::Big, tcx, param_env)
+    }
+
+    /// Like `u32::to_le`, for integer constants: swaps the bytes if the target is big endian and
+    /// only evaluates the constant otherwise.
+    pub fn try_to_le(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<Const<'tcx>> {
+        self.try_swap_bytes_unless(Endian::Little, tcx, param_env)
+    }
+
+    fn try_swap_bytes_unless(
+        self,
+        endian: Endian,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        if tcx.data_layout.endian != endian {
+            return self.try_swap_bytes(tcx, param_env);
+        }
+        if !self.ty().is_integral() {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(int), self.ty()))
+    }
+
+    /// Evaluates a constant of an integer, float, `bool` or `char` type and reinterprets its bits
+    /// as a constant of type `to_ty`, which must be one of those types as well, like `transmute`.
+    /// Returns `None` if the sizes of the types differ, if the bits aren't a valid `bool` or
+    /// `char` when transmuting to those, or if the constant can't be evaluated. Pointers are
+    /// rejected, as their provenance can't be transmuted away.
+    pub fn try_transmute_scalar(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        to_ty: Ty<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        let is_scalar = |ty: Ty<'tcx>| {
+            matches!(ty.kind(), ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) | ty::Float(_))
+        };
+        if !is_scalar(self.ty()) || !is_scalar(to_ty) {
+            return None;
+        }
+        let size = tcx.layout_of(param_env.with_reveal_all_normalized(tcx).and(to_ty)).ok()?.size;
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?.try_transmute(size)?;
+        let valid = match to_ty.kind() {
+            ty::Bool => int.try_to_bool().is_ok(),
+            ty::Char => char::try_from(int).is_ok(),
+            _ => true,
+        };
+        valid.then(|| ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(int), to_ty))
+    }
+
+    /// Evaluates a signed integer or float constant and returns its absolute value. For the
+    /// minimum value of a signed integer type this returns `None`, as `abs` overflows there.
+    /// Also returns `None` for other types and for constants that can't be evaluated.
+    pub fn try_abs(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<Const<'tcx>> {
+        let int = match self.ty().kind() {
+            ty::Int(_) | ty::Float(_) => self.kind().eval(tcx, param_env).try_to_scalar_int()?,
+            _ => return None,
+        };
+        let abs =
+            if self.ty().is_floating_point() { int.float_abs() } else { int.checked_abs_int()? };
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(abs), self.ty()))
+    }
+
+    /// Evaluates a signed integer or float constant and returns its sign as a constant of the same
+    /// type: `-1`, `0` or `1` for integers, and `-1.0`, `1.0` or NaN for floats. Like
+    /// `f32::signum`, `-0.0` has the sign `-1.0`, and NaN produces the default NaN. Returns `None`
+    /// for other types and for constants that can't be evaluated.
+    pub fn try_signum(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<Const<'tcx>> {
+        let int = match self.ty().kind() {
+            ty::Int(_) | ty::Float(_) => self.kind().eval(tcx, param_env).try_to_scalar_int()?,
+            _ => return None,
+        };
+        let signum =
+            if self.ty().is_floating_point() { int.float_signum()? } else { int.signum_int() };
+        Some(ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(signum), self.ty()))
+    }
+
+    /// Evaluates an `f32` or `f64` constant and classifies its value, keeping the distinctions
+    /// between NaNs that are lost when reading it as a host float. Returns `None` for other types
+    /// and for constants that can't be evaluated.
+    pub fn try_eval_f64_checked(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<FloatCategory> {
+        if !self.ty().is_floating_point() {
+            return None;
+        }
+        self.kind().eval(tcx, param_env).try_to_scalar_int()?.float_category()
+    }
+
+    /// Evaluates a constant of any integer or float type and converts its value to an `f64`, for
+    /// code that treats all numbers alike. This is lossy: integers with more than 53 significant
+    /// bits are rounded to the nearest `f64` like an `as` cast does, so e.g. `i128::MAX` becomes
+    /// `2^127`. `f32` values are converted exactly. Returns `None` for other types and for
+    /// constants that can't be evaluated.
+    pub fn try_eval_as_f64(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<f64> {
+        let ty = self.ty();
+        if !(ty.is_integral() || ty.is_floating_point()) {
+            return None;
+        }
+        let int = self.kind().eval(tcx, param_env).try_to_scalar_int()?;
+        if ty.is_floating_point() {
+            int.float_to_f64()
+        } else {
+            Some(int.int_to_f64_lossy(ty.is_signed()))
+        }
+    }
+
+    #[inline]
+    /// Reads the value of an already evaluated `bool` constant. Unlike `try_eval_bool`, this
+    /// never evaluates anything, so it returns `None` for constants that aren't values yet.
+    pub fn as_bool(self) -> Option<bool> {
+        if !self.ty().is_bool() {
+            return None;
+        }
+        self.kind().try_to_bool()
+    }
+
     #[inline]
     pub fn try_eval_bool(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<bool> {
         self.kind().eval(tcx, param_env).try_to_bool()
     }
 
+    /// Evaluates a `bool` constant, or returns `default` if it can't be evaluated, e.g. because
+    /// it is still generic or evaluation failed. This is for lints and tools that have to make a
+    /// decision either way.
+    #[inline]
+    pub fn eval_bool_or(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>, default: bool) -> bool {
+        self.try_eval_bool(tcx, param_env).unwrap_or(default)
+    }
+
+    /// Evaluates an unsigned integer constant and converts it to an alignment. Returns `None` if
+    /// the value isn't a power of two, is zero or too large, or if the constant isn't an unsigned
+    /// integer or can't be evaluated.
+    pub fn try_eval_align(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<Align> {
+        if !matches!(self.ty().kind(), ty::Uint(_)) {
+            return None;
+        }
+        self.kind().eval(tcx, param_env).try_to_scalar_int()?.try_to_align()
+    }
+
     #[inline]
     pub fn try_eval_target_usize(
         self,
@@ -310,6 +966,723 @@ pub fn try_eval_target_usize(
         self.kind().eval(tcx, param_env).try_to_target_usize(tcx)
     }
 
+    /// Returns the length of the array type of this constant. Only the length in the type is
+    /// evaluated, not the constant itself. Returns `None` if the type isn't an array or its
+    /// length can't be evaluated, e.g. because it is still generic.
+    pub fn try_eval_array_len(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<u64> {
+        let ty::Array(_, len) = *self.ty().kind() else {
+            return None;
+        };
+        len.try_eval_target_usize(tcx, param_env)
+    }
+
+    /// Evaluates a constant of array type, or a reference to an array or slice, and returns its
+    /// element at `idx`. Returns `None` if `idx` is out of bounds, if the constant can't be
+    /// evaluated, or if it can't be indexed.
+    pub fn try_index(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        idx: u64,
+    ) -> Option<Const<'tcx>> {
+        let elem_ty = self.indexable_elem_ty()?;
+        let elem = self.kind().eval(tcx, param_env).try_to_value()?.try_get_element(idx)?;
+        Some(ty::Const::new_value(tcx, elem, elem_ty))
+    }
+
+    /// Evaluates a constant of array type, or a reference to an array or slice, and returns its
+    /// first element like `<[T]>::first`: the outer `Option` is `None` if the constant can't be
+    /// evaluated or indexed, the inner one is `None` if it is empty.
+    pub fn try_first(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Option<Const<'tcx>>> {
+        let elem_ty = self.indexable_elem_ty()?;
+        let elem = self.kind().eval(tcx, param_env).try_to_value()?.try_first_element()?;
+        Some(elem.map(|elem| ty::Const::new_value(tcx, elem, elem_ty)))
+    }
+
+    /// Like `try_first`, but returns the last element like `<[T]>::last`.
+    pub fn try_last(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Option<Const<'tcx>>> {
+        let elem_ty = self.indexable_elem_ty()?;
+        let elem = self.kind().eval(tcx, param_env).try_to_value()?.try_last_element()?;
+        Some(elem.map(|elem| ty::Const::new_value(tcx, elem, elem_ty)))
+    }
+
+    /// Returns the element type of an array, or of a reference to an array or slice.
+    fn indexable_elem_ty(self) -> Option<Ty<'tcx>> {
+        match *self.ty().kind() {
+            ty::Array(elem_ty, _) => Some(elem_ty),
+            ty::Ref(_, inner_ty, _) => match *inner_ty.kind() {
+                ty::Array(elem_ty, _) | ty::Slice(elem_ty) => Some(elem_ty),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Evaluates a constant of array type, or a reference to an array or slice, and returns the
+    /// elements `start..end` as a constant of the same kind, sharing the evaluated elements of
+    /// `self`. Arrays produce an array of length `end - start`, references to arrays produce a
+    /// reference to such an array, and slices produce a slice. Returns `None` if the range is out
+    /// of bounds, if the constant can't be evaluated, or if it can't be sliced.
+    pub fn try_subslice(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        start: u64,
+        end: u64,
+    ) -> Option<Const<'tcx>> {
+        let ty = self.subslice_ty(tcx, end.checked_sub(start)?)?;
+        let elems = self.kind().eval(tcx, param_env).try_to_value()?.try_subslice(start, end)?;
+        Some(ty::Const::new_value(tcx, elems, ty))
+    }
+
+    /// Evaluates a constant of array type, or a reference to an array or slice, and splits it into
+    /// the elements before `mid` and the elements from `mid` on, like `<[T]>::split_at`. The
+    /// halves are constants of the same kind as `self`, like the results of `try_subslice`, and
+    /// share the evaluated elements of `self`. Returns `None` if `mid` is greater than the length,
+    /// if the constant can't be evaluated, or if it can't be sliced.
+    pub fn try_split_at(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        mid: u64,
+    ) -> Option<(Const<'tcx>, Const<'tcx>)> {
+        let head_ty = self.subslice_ty(tcx, mid)?;
+        let (head, tail) = self.kind().eval(tcx, param_env).try_to_value()?.try_split_at(mid)?;
+        let tail_ty = self.subslice_ty(tcx, tail.unwrap_branch().len() as u64)?;
+        Some((ty::Const::new_value(tcx, head, head_ty), ty::Const::new_value(tcx, tail, tail_ty)))
+    }
+
+    /// Evaluates a constant of array type, or a reference to an array or slice, and splits its
+    /// elements into chunks of `chunk_size` elements, like `<[T]>::chunks`. The last chunk is
+    /// shorter if the length isn't a multiple of `chunk_size`. The chunks are constants of the
+    /// same kind as `self`, like the results of `try_subslice`, and share the evaluated elements
+    /// of `self`. Returns `None` if `chunk_size` is zero, if the constant can't be evaluated, or
+    /// if it can't be sliced.
+    pub fn try_chunks(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        chunk_size: u64,
+    ) -> Option<Vec<Const<'tcx>>> {
+        self.subslice_ty(tcx, chunk_size)?;
+        let chunks = self.kind().eval(tcx, param_env).try_to_value()?.try_chunks(chunk_size)?;
+        chunks
+            .into_iter()
+            .map(|chunk| {
+                let ty = self.subslice_ty(tcx, chunk.unwrap_branch().len() as u64)?;
+                Some(ty::Const::new_value(tcx, chunk, ty))
+            })
+            .collect()
+    }
+
+    /// Evaluates a constant of array type, or a reference to an array or slice, and returns all
+    /// overlapping windows of `size` elements, like `<[T]>::windows`. The windows are constants of
+    /// the same kind as `self`, like the results of `try_subslice`, and share the evaluated
+    /// elements of `self`. Returns `None` if `size` is zero or greater than the length, if the
+    /// constant can't be evaluated, or if it can't be sliced.
+    pub fn try_windows(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        size: u64,
+    ) -> Option<Vec<Const<'tcx>>> {
+        let ty = self.subslice_ty(tcx, size)?;
+        let windows = self.kind().eval(tcx, param_env).try_to_value()?.try_windows(size)?;
+        Some(windows.into_iter().map(|window| ty::Const::new_value(tcx, window, ty)).collect())
+    }
+
+    /// Returns the type of `len` elements of an array or a reference to an array or slice, as
+    /// returned by `try_subslice`.
+    fn subslice_ty(self, tcx: TyCtxt<'tcx>, len: u64) -> Option<Ty<'tcx>> {
+        match *self.ty().kind() {
+            ty::Array(elem_ty, _) => Some(tcx.mk_array(elem_ty, len)),
+            ty::Ref(region, inner_ty, mutbl) => match *inner_ty.kind() {
+                ty::Array(elem_ty, _) => Some(
+                    tcx.mk_ref(region, ty::TypeAndMut { ty: tcx.mk_array(elem_ty, len), mutbl }),
+                ),
+                ty::Slice(_) => Some(self.ty()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Evaluates a constant of array type and `needle`, and returns whether the array contains
+    /// `needle`, like `<[T]>::contains`. Only integer, `bool` and `char` elements are supported,
+    /// for which `==` is structural equality. Returns `None` for other types, if `needle` has a
+    /// different type than the elements, or if either constant can't be evaluated.
+    pub fn array_contains(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        needle: Const<'tcx>,
+    ) -> Option<bool> {
+        let ty::Array(elem_ty, _) = *self.ty().kind() else {
+            return None;
+        };
+        if !(elem_ty.is_integral() || elem_ty.is_bool() || elem_ty.is_char())
+            || needle.ty() != elem_ty
+        {
+            return None;
+        }
+        let needle = needle.kind().eval(tcx, param_env).try_to_value()?;
+        self.kind().eval(tcx, param_env).try_to_value()?.branch_contains(needle)
+    }
+
+    /// Evaluates a constant of array type with integer elements and calls `f` with the bits of
+    /// every element, in order. Returns `false` without calling `f` if the constant can't be
+    /// evaluated or isn't such an array.
+    pub fn for_each_array_element_bits(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        mut f: impl FnMut(u128),
+    ) -> bool {
+        let ty::Array(elem_ty, _) = *self.ty().kind() else {
+            return false;
+        };
+        if !elem_ty.is_integral() {
+            return false;
+        }
+        let Ok(layout) = tcx.layout_of(param_env.with_reveal_all_normalized(tcx).and(elem_ty))
+        else {
+            return false;
+        };
+        let Some(ty::ValTree::Branch(elems)) = self.kind().eval(tcx, param_env).try_to_value()
+        else {
+            return false;
+        };
+        // Check every element before calling `f`, so callers never see a partial array.
+        let bits = |elem: &ty::ValTree<'tcx>| elem.try_to_scalar_int()?.to_bits(layout.size).ok();
+        if !elems.iter().all(|elem| bits(elem).is_some()) {
+            return false;
+        }
+        elems.iter().filter_map(bits).for_each(&mut f);
+        true
+    }
+
+    /// Evaluates a constant of type `[[bool; M]; N]` and returns its elements row by row, i.e.
+    /// the outer `Vec` has `N` entries of length `M`. Returns `None` if the constant can't be
+    /// evaluated or has a different type.
+    pub fn try_eval_bool_matrix(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Vec<Vec<bool>>> {
+        let ty::Array(row_ty, _) = *self.ty().kind() else {
+            return None;
+        };
+        let ty::Array(elem_ty, _) = *row_ty.kind() else {
+            return None;
+        };
+        if !elem_ty.is_bool() {
+            return None;
+        }
+        let ty::ValTree::Branch(rows) = self.kind().eval(tcx, param_env).try_to_value()? else {
+            return None;
+        };
+        rows.iter()
+            .map(|row| {
+                let ty::ValTree::Branch(elems) = row else {
+                    return None;
+                };
+                elems.iter().map(|elem| elem.try_to_scalar_int()?.try_to_bool().ok()).collect()
+            })
+            .collect()
+    }
+
+    /// Evaluates a constant of type `[char; N]` and returns its elements. Returns `None` if the
+    /// constant can't be evaluated, has a different type, or contains an invalid `char`.
+    pub fn try_eval_char_array(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Vec<char>> {
+        let ty::Array(elem_ty, _) = *self.ty().kind() else {
+            return None;
+        };
+        if !elem_ty.is_char() {
+            return None;
+        }
+        self.kind().eval(tcx, param_env).try_to_value()?.try_to_chars()
+    }
+
+    /// Evaluates a `&str` constant and returns the number of `char`s in it, not the number of
+    /// bytes. Returns `None` if the constant can't be evaluated or isn't a `&str`.
+    pub fn try_eval_char_count(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<usize> {
+        let ty::Ref(_, inner_ty, _) = self.ty().kind() else {
+            return None;
+        };
+        if !inner_ty.is_str() {
+            return None;
+        }
+        let bytes =
+            self.kind().eval(tcx, param_env).try_to_value()?.try_to_raw_bytes(tcx, self.ty())?;
+        Some(std::str::from_utf8(bytes).ok()?.chars().count())
+    }
+
+    /// Evaluates a constant of type `[u8; N]` and returns its bytes. Returns `None` if the
+    /// constant can't be evaluated or has a different type.
+    pub fn try_eval_byte_array(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Vec<u8>> {
+        let ty::Array(elem_ty, _) = *self.ty().kind() else {
+            return None;
+        };
+        if *elem_ty.kind() != ty::Uint(ty::UintTy::U8) {
+            return None;
+        }
+        let bytes =
+            self.kind().eval(tcx, param_env).try_to_value()?.try_to_raw_bytes(tcx, self.ty())?;
+        Some(bytes.to_vec())
+    }
+
+    /// Evaluates a constant of a tuple type whose fields are all `u8`, like `(u8, u8, u8, u8)`,
+    /// and returns its fields in order. Returns `None` if the constant can't be evaluated or has a
+    /// different type.
+    pub fn try_eval_byte_tuple(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Vec<u8>> {
+        let ty::Tuple(tys) = self.ty().kind() else {
+            return None;
+        };
+        if !tys.iter().all(|ty| *ty.kind() == ty::Uint(ty::UintTy::U8)) {
+            return None;
+        }
+        self.kind().eval(tcx, param_env).try_to_value()?.try_to_u8s()
+    }
+
+    /// Evaluates a constant of fat pointer type (`&str`, `&[T]`, `&dyn Trait`, ...) and returns
+    /// its data pointer and its metadata (a length or a vtable pointer). Returns `None` if the
+    /// constant can't be evaluated or isn't a fat pointer.
+    pub fn try_eval_fat_pointer(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<(Scalar, Scalar)> {
+        let pointee = self.ty().builtin_deref(true)?.ty;
+        if pointee.is_sized(tcx, param_env) {
+            return None;
+        }
+        match self.try_eval_const_value(tcx, param_env)? {
+            ConstValue::Slice { data, start, end } => {
+                let ptr = Pointer::new(tcx.create_memory_alloc(data), Size::from_bytes(start));
+                let len = Scalar::from_target_usize((end - start) as u64, &tcx);
+                Some((Scalar::from_pointer(ptr, &tcx), len))
+            }
+            ConstValue::ByRef { alloc, offset } => {
+                let ptr_size = tcx.data_layout.pointer_size;
+                let alloc = alloc.inner();
+                let data = alloc.read_scalar(&tcx, alloc_range(offset, ptr_size), true).ok()?;
+                let meta =
+                    alloc.read_scalar(&tcx, alloc_range(offset + ptr_size, ptr_size), true).ok()?;
+                Some((data, meta))
+            }
+            ConstValue::Scalar(_) | ConstValue::ZeroSized => None,
+        }
+    }
+
+    /// Evaluates a constant holding a pointer, like a reference, a raw pointer or a `NonNull`, and
+    /// returns the allocation it points into and the offset within that allocation. For
+    /// `&ARR[2]`, this is the allocation of `ARR` and twice the size of its elements. For fat
+    /// pointers, this is the target of the data pointer. Returns `None` if the pointer has no
+    /// provenance, e.g. because it is null, if the constant isn't a pointer, or if it can't be
+    /// evaluated.
+    pub fn try_eval_pointer_target(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<(AllocId, Size)> {
+        let ptr = match self.try_eval_const_value(tcx, param_env)? {
+            ConstValue::Scalar(scalar) => scalar,
+            ConstValue::Slice { .. } | ConstValue::ByRef { .. } => {
+                self.try_eval_fat_pointer(tcx, param_env)?.0
+            }
+            ConstValue::ZeroSized => return None,
+        };
+        match ptr {
+            Scalar::Ptr(ptr, _) => Some(ptr.into_parts()),
+            Scalar::Int(_) => None,
+        }
+    }
+
+    /// Evaluates a constant and returns the raw bytes of the memory backing it, together with the
+    /// offset and target of every pointer stored in those bytes. Uninitialized bytes are returned
+    /// as whatever the allocation happens to contain. Returns `None` if the constant can't be
+    /// evaluated or has no backing memory, like scalars.
+    pub fn try_eval_raw_bytes(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<(Vec<u8>, Vec<(Size, AllocId)>)> {
+        let (alloc, start, size) = match self.try_eval_const_value(tcx, param_env)? {
+            ConstValue::ByRef { alloc, offset } => {
+                let layout =
+                    tcx.layout_of(param_env.with_reveal_all_normalized(tcx).and(self.ty()));
+                (alloc, offset, layout.ok()?.size)
+            }
+            ConstValue::Slice { data, start, end } => {
+                (data, Size::from_bytes(start), Size::from_bytes(end - start))
+            }
+            ConstValue::Scalar(_) | ConstValue::ZeroSized => return None,
+        };
+        let alloc = alloc.inner();
+        let end = start + size;
+        let bytes = alloc.inspect_with_uninit_and_ptr_outside_interpreter(
+            start.bytes_usize()..end.bytes_usize(),
+        );
+        let ptrs = alloc
+            .provenance()
+            .ptrs()
+            .range(start..end)
+            .iter()
+            .map(|&(offset, alloc_id)| (offset - start, alloc_id))
+            .collect();
+        Some((bytes.to_vec(), ptrs))
+    }
+
+    /// Evaluates a constant and returns the byte at `offset` of its memory representation. For a
+    /// reference to a slice or `str`, this is the memory it points to, like in
+    /// `try_eval_raw_bytes`. Returns `None` if `offset` is out of range, if the byte is
+    /// uninitialized or part of a pointer, or if the constant can't be evaluated.
+    pub fn try_eval_byte_at(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        offset: u64,
+    ) -> Option<u8> {
+        let offset = Size::from_bytes(offset);
+        let val = self.try_eval_const_value(tcx, param_env)?;
+        if let ConstValue::Scalar(Scalar::Int(int)) = val {
+            let mut bytes = vec![0; int.size().bytes_usize()];
+            write_target_uint(tcx.data_layout.endian, &mut bytes, int.assert_bits(int.size()))
+                .ok()?;
+            return bytes.get(offset.bytes_usize()).copied();
+        }
+        let (alloc, range) = self.memory_of_value(tcx, param_env, val)?;
+        if offset >= range.size {
+            return None;
+        }
+        alloc.inner().inspect_byte(range.start + offset, &tcx)
+    }
+
+    /// Evaluates two constants of the same type and returns whether their memory is identical,
+    /// including which bytes are initialized and the provenance of the pointers in it. This is
+    /// stricter than comparing their valtrees: two `&[&u8]` slices that point to different but
+    /// equal bytes are not equal here. For a reference to a slice or `str`, this compares the
+    /// memory it points to. Returns `None` for scalar and zero-sized constants, which should be
+    /// compared by value, if the types differ, or if one of the constants can't be evaluated.
+    pub fn bytes_eq(
+        self,
+        other: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<bool> {
+        if self.ty() != other.ty() {
+            return None;
+        }
+        let (alloc, range) =
+            self.memory_of_value(tcx, param_env, self.try_eval_const_value(tcx, param_env)?)?;
+        let (other_alloc, other_range) =
+            other.memory_of_value(tcx, param_env, other.try_eval_const_value(tcx, param_env)?)?;
+        Some(alloc.inner().range_bytes_eq(range, other_alloc.inner(), other_range, &tcx))
+    }
+
+    /// Evaluates a constant and returns a hash of the bytes of its value that is stable across
+    /// compilations and hosts, to check whether a constant changed between two builds. This uses
+    /// `Allocation::range_content_hash`, see there for the exact algorithm; scalar and zero-sized
+    /// values are hashed like memory holding their bytes, in the target's endianness. For a
+    /// reference to a slice or `str`, the memory it points to is hashed. Returns `None` if the
+    /// value contains pointers, for which the content is ill-defined, or if the constant can't be
+    /// evaluated.
+    pub fn content_hash(self, tcx: TyCtxt<'tcx>, param_env: ParamEnv<'tcx>) -> Option<u128> {
+        let val = self.try_eval_const_value(tcx, param_env)?;
+        let bytes = match val {
+            ConstValue::Scalar(Scalar::Ptr(..)) => return None,
+            ConstValue::Scalar(Scalar::Int(int)) => {
+                let mut bytes = vec![0; int.size().bytes_usize()];
+                write_target_uint(tcx.data_layout.endian, &mut bytes, int.assert_bits(int.size()))
+                    .ok()?;
+                bytes
+            }
+            ConstValue::ZeroSized => vec![],
+            ConstValue::ByRef { .. } | ConstValue::Slice { .. } => {
+                let (alloc, range) = self.memory_of_value(tcx, param_env, val)?;
+                return alloc.inner().range_content_hash(range, &tcx);
+            }
+        };
+        let alloc: Allocation = Allocation::from_bytes_byte_aligned_immutable(bytes);
+        alloc.range_content_hash(alloc_range(Size::ZERO, alloc.size()), &tcx)
+    }
+
+    /// Returns the memory that holds `val`, the value of this constant, or `None` if it is a
+    /// scalar or zero-sized. For a reference to a slice or `str`, this is the memory it points to.
+    fn memory_of_value(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        val: ConstValue<'tcx>,
+    ) -> Option<(ConstAllocation<'tcx>, AllocRange)> {
+        match val {
+            ConstValue::ByRef { alloc, offset } => {
+                let layout =
+                    tcx.layout_of(param_env.with_reveal_all_normalized(tcx).and(self.ty()));
+                Some((alloc, alloc_range(offset, layout.ok()?.size)))
+            }
+            ConstValue::Slice { data, start, end } => {
+                Some((data, alloc_range(Size::from_bytes(start), Size::from_bytes(end - start))))
+            }
+            ConstValue::Scalar(_) | ConstValue::ZeroSized => None,
+        }
+    }
+
+    /// Evaluates a constant of enum type and returns the raw bits of its discriminant, without
+    /// reading any of the fields of the active variant. Returns `None` if the constant can't be
+    /// evaluated or isn't an enum.
+    pub fn try_eval_discriminant(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<u128> {
+        if !self.ty().is_enum() {
+            return None;
+        }
+        let val = self.try_eval_const_value(tcx, param_env)?;
+        tcx.try_read_mir_constant_discriminant(
+            param_env.and(mir::ConstantKind::Val(val, self.ty())),
+        )
+    }
+
+    /// Evaluates a constant of enum type and checks whether its active variant is `variant`.
+    /// This only reads the discriminant, which makes it a cheap way to fold methods like
+    /// `Option::is_some`. Returns `None` if the constant can't be evaluated or isn't an enum.
+    pub fn is_variant(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        variant: VariantIdx,
+    ) -> Option<bool> {
+        let ty::Adt(def, _) = self.ty().kind() else {
+            return None;
+        };
+        let discr = self.try_eval_discriminant(tcx, param_env)?;
+        Some(def.discriminant_for_variant(tcx, variant).val == discr)
+    }
+
+    /// Evaluates a constant of enum type and checks whether its active variant is one of
+    /// `variants`, which folds `matches!(CONST, A | B)`. Like `is_variant`, this only reads the
+    /// discriminant. Returns `None` if the constant can't be evaluated or isn't an enum.
+    pub fn discriminant_in(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        variants: &[VariantIdx],
+    ) -> Option<bool> {
+        let ty::Adt(def, _) = self.ty().kind() else {
+            return None;
+        };
+        let discr = self.try_eval_discriminant(tcx, param_env)?;
+        Some(
+            variants.iter().any(|&variant| def.discriminant_for_variant(tcx, variant).val == discr),
+        )
+    }
+
+    /// Evaluates a constant of enum type and returns the name of its active variant, e.g. `Red`
+    /// for `Color::Red`. This only reads the discriminant, like `try_eval_discriminant`. Returns
+    /// `None` if the constant can't be evaluated or isn't an enum.
+    pub fn try_eval_variant_name(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Symbol> {
+        let ty::Adt(def, _) = self.ty().kind() else {
+            return None;
+        };
+        let discr = self.try_eval_discriminant(tcx, param_env)?;
+        let (variant, _) = def.discriminants(tcx).find(|(_, d)| d.val == discr)?;
+        Some(def.variant(variant).name)
+    }
+
+    /// Evaluates a constant of type `Option<T>` and returns the value in it if it is `Some`, or
+    /// `default`, which must be of type `T`, if it is `None`, like `Option::unwrap_or`. Returns
+    /// `None` if the constant isn't an `Option`, `default` has a different type, or one of the
+    /// constants can't be evaluated.
+    pub fn try_unwrap_or(
+        self,
+        default: Const<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        let ty::Adt(def, substs) = self.ty().kind() else {
+            return None;
+        };
+        if tcx.lang_items().option_type() != Some(def.did()) {
+            return None;
+        }
+        let inner_ty = substs.type_at(0);
+        if default.ty() != inner_ty {
+            return None;
+        }
+        let (variant, fields) =
+            self.kind().eval(tcx, param_env).try_to_value()?.try_to_variant()?;
+        let val = if tcx.lang_items().option_some_variant() == Some(def.variant(variant).def_id) {
+            *fields.first()?
+        } else {
+            default.kind().eval(tcx, param_env).try_to_value()?
+        };
+        Some(ty::Const::new_value(tcx, val, inner_ty))
+    }
+
+    /// Evaluates a constant of type `Result<T, E>` and converts it to an `Option<T>` like
+    /// `Result::ok`: `Ok(v)` becomes `Some(v)` and any `Err` becomes `None`. Returns `None` if the
+    /// constant isn't a `Result` or can't be evaluated.
+    pub fn try_result_ok(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Const<'tcx>> {
+        let ty::Adt(def, substs) = self.ty().kind() else {
+            return None;
+        };
+        let lang_items = tcx.lang_items();
+        let ok_variant = lang_items.result_ok_variant()?;
+        if tcx.parent(ok_variant) != def.did() {
+            return None;
+        }
+        let (variant, fields) =
+            self.kind().eval(tcx, param_env).try_to_value()?.try_to_variant()?;
+        let option_def = tcx.adt_def(lang_items.option_type()?);
+        let (option_variant, fields) = if def.variant(variant).def_id == ok_variant {
+            (lang_items.option_some_variant()?, fields)
+        } else {
+            (lang_items.option_none_variant()?, &[][..])
+        };
+        let elems = ty::ValTree::variant_branch_elems(
+            option_def.variant_index_with_id(option_variant),
+            fields,
+        );
+        let ty = tcx.mk_adt(option_def, tcx.mk_substs(&[substs[0]]));
+        Some(ty::Const::new_value(tcx, ty::ValTree::Branch(tcx.arena.alloc_from_iter(elems)), ty))
+    }
+
+    /// Evaluates a constant of tuple struct type and returns each of its fields as a separate
+    /// type-level constant. Returns `None` if the constant can't be evaluated, isn't a tuple
+    /// struct, or one of its fields has no type-level representation.
+    pub fn try_eval_tuple_struct_fields(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Vec<Const<'tcx>>> {
+        let ty::Adt(def, _) = self.ty().kind() else {
+            return None;
+        };
+        if !def.is_struct() || def.non_enum_variant().ctor_kind() != Some(CtorKind::Fn) {
+            return None;
+        }
+        let val = self.try_eval_const_value(tcx, param_env)?;
+        let destructured = tcx
+            .try_destructure_mir_constant(param_env.and(mir::ConstantKind::Val(val, self.ty())))?;
+        destructured
+            .fields
+            .iter()
+            .map(|field| match *field {
+                mir::ConstantKind::Val(val, ty) => Const::from_const_value(tcx, val, ty),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Evaluates a constant of a struct type with named fields and returns the name and value of
+    /// each field, in declaration order. Returns `None` if the constant can't be evaluated, isn't
+    /// such a struct, or one of its fields isn't a scalar.
+    pub fn try_eval_named_fields(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<Vec<(Symbol, ScalarInt)>> {
+        let ty::Adt(def, _) = self.ty().kind() else {
+            return None;
+        };
+        if !def.is_struct() || def.non_enum_variant().ctor_kind().is_some() {
+            return None;
+        }
+        let ty::ValTree::Branch(fields) = self.kind().eval(tcx, param_env).try_to_value()? else {
+            return None;
+        };
+        iter::zip(&def.non_enum_variant().fields, fields)
+            .map(|(field, val)| Some((field.name, val.try_to_scalar_int()?)))
+            .collect()
+    }
+
+    /// Evaluates a constant made of exactly two scalars, like a `(usize, usize)` tuple, a struct
+    /// with two integer fields, or a `Range<usize>`, and returns the bits of both fields without
+    /// allocating. Returns `None` if the constant can't be evaluated or has a different shape.
+    pub fn try_eval_scalar_pair_ints(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<(u128, u128)> {
+        let field_count = match self.ty().kind() {
+            ty::Tuple(tys) => tys.len(),
+            ty::Adt(def, _) if def.is_struct() => def.non_enum_variant().fields.len(),
+            _ => return None,
+        };
+        if field_count != 2 {
+            return None;
+        }
+        self.kind().eval(tcx, param_env).try_to_value()?.try_to_scalar_int_pair()
+    }
+
+    /// Evaluates a `Range` or `RangeInclusive` constant over an integer type, like `0..10` or
+    /// `5..=5`, and returns the bits of its start and end. Returns `None` for other types or if
+    /// the constant can't be evaluated.
+    pub fn try_eval_range_ints(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<(u128, u128)> {
+        let ty::Adt(def, substs) = self.ty().kind() else {
+            return None;
+        };
+        let lang_items = tcx.lang_items();
+        if ![lang_items.range_struct(), lang_items.range_inclusive_struct()]
+            .contains(&Some(def.did()))
+            || !substs.type_at(0).is_integral()
+        {
+            return None;
+        }
+        self.kind().eval(tcx, param_env).try_to_value()?.try_to_range_bounds()
+    }
+
+    /// Tries to evaluate the constant to a `ConstValue`. Unlike `eval`, this also works for
+    /// constants that have no valtree representation, such as `&dyn Trait`.
+    fn try_eval_const_value(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ParamEnv<'tcx>,
+    ) -> Option<ConstValue<'tcx>> {
+        match self.kind() {
+            ty::ConstKind::Value(valtree) => Some(tcx.valtree_to_const_val((self.ty(), valtree))),
+            kind => kind.try_eval_for_mir(tcx, param_env)?.ok(),
+        }
+    }
+
     #[inline]
     /// Tries to evaluate the constant if it is `Unevaluated`. If that doesn't succeed, return the
     /// unevaluated constant.
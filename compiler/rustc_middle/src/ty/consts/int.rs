@@ -1,13 +1,18 @@
 use rustc_apfloat::ieee::{Double, Single};
-use rustc_apfloat::Float;
+use rustc_apfloat::{Float, FloatConvert};
 use rustc_errors::{DiagnosticArgValue, IntoDiagnosticArg};
 use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
-use rustc_target::abi::Size;
+use rustc_target::abi::{Align, Size};
+use std::cmp::Ordering;
 use std::fmt;
 use std::num::NonZeroU8;
 
+use crate::mir::BinOp;
 use crate::ty::TyCtxt;
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Copy, Clone)]
 /// A type for representing any integer. Only used for printing.
 pub struct ConstInt {
@@ -352,6 +357,395 @@ pub fn try_to_i64(self) -> Result<i64, Size> {
     pub fn try_to_i128(self) -> Result<i128, Size> {
         self.try_to_int(Size::from_bits(128))
     }
+
+    /// Computes the absolute value of `self` interpreted as a signed integer. Like
+    /// `i32::checked_abs`, this returns `None` for the minimum value of the type, whose absolute
+    /// value doesn't fit.
+    pub fn checked_abs_int(self) -> Option<Self> {
+        let size = self.size();
+        let val = size.sign_extend(self.data) as i128;
+        Self::try_from_int(val.checked_abs()?, size)
+    }
+
+    /// Computes `self op other` for two integers of the same size, wrapping around on overflow.
+    /// Returns the result together with whether it overflowed, just like the interpreter does for
+    /// checked arithmetic. Only `Add`, `Sub` and `Mul` are supported; returns `None` for other
+    /// operators or if the sizes differ.
+    pub fn overflowing_int_op(self, op: BinOp, other: Self, signed: bool) -> Option<(Self, bool)> {
+        let size = self.size();
+        if other.size() != size {
+            return None;
+        }
+        let (data, overflow) = if signed {
+            let (l, r) =
+                (size.sign_extend(self.data) as i128, size.sign_extend(other.data) as i128);
+            let (res, overflow) = match op {
+                BinOp::Add => l.overflowing_add(r),
+                BinOp::Sub => l.overflowing_sub(r),
+                BinOp::Mul => l.overflowing_mul(r),
+                _ => return None,
+            };
+            let truncated = size.truncate(res as u128);
+            (truncated, overflow || size.sign_extend(truncated) as i128 != res)
+        } else {
+            let (l, r) = (self.data, other.data);
+            let (res, overflow) = match op {
+                BinOp::Add => l.overflowing_add(r),
+                BinOp::Sub => l.overflowing_sub(r),
+                BinOp::Mul => l.overflowing_mul(r),
+                _ => return None,
+            };
+            let truncated = size.truncate(res);
+            (truncated, overflow || truncated != res)
+        };
+        Some((Self { data, size: self.size }, overflow))
+    }
+
+    /// Adds up `elems`, which must all be of size `size`, like `Iterator::sum` does for numbers of
+    /// the corresponding type, but wraps around on integer overflow instead of panicking. With
+    /// `float`, the elements are `f32` or `f64` values, which are added in order starting at
+    /// `0.0`. Returns `None` if an element has a different size, or if `float` is set and `size`
+    /// isn't that of a float type.
+    pub fn sum(elems: impl IntoIterator<Item = Self>, size: Size, float: bool) -> Option<Self> {
+        fn float_sum<F: Float + Into<ScalarInt> + TryFrom<ScalarInt>>(
+            elems: impl Iterator<Item = ScalarInt>,
+        ) -> Option<ScalarInt> {
+            let mut sum = F::ZERO;
+            for elem in elems {
+                sum = (sum + F::try_from(elem).ok()?).value;
+            }
+            Some(sum.into())
+        }
+        let mut elems = elems.into_iter();
+        if !float {
+            // Wrapping addition gives the same bits for signed and unsigned integers.
+            return elems.try_fold(Self::null(size), |sum, elem| {
+                Some(sum.overflowing_int_op(BinOp::Add, elem, false)?.0)
+            });
+        }
+        match size.bytes() {
+            4 => float_sum::<Single>(elems),
+            8 => float_sum::<Double>(elems),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of ones in the binary representation of `self`.
+    #[inline]
+    pub fn count_ones(self) -> u32 {
+        { self.data }.count_ones()
+    }
+
+    /// Returns the number of zeros in the binary representation of `self`, only counting the
+    /// bits within its size.
+    #[inline]
+    pub fn count_zeros(self) -> u32 {
+        self.size().bits() as u32 - self.count_ones()
+    }
+
+    /// Returns the number of leading zeros in the binary representation of `self`, only counting
+    /// the bits within its size. So for a zero of size 1, this is 8 rather than 128.
+    #[inline]
+    pub fn leading_zeros(self) -> u32 {
+        { self.data }.leading_zeros() - (128 - self.size().bits() as u32)
+    }
+
+    /// Returns the number of trailing zeros in the binary representation of `self`, which is at
+    /// most the number of bits of its size.
+    #[inline]
+    pub fn trailing_zeros(self) -> u32 {
+        { self.data }.trailing_zeros().min(self.size().bits() as u32)
+    }
+
+    /// Rotates the bits of `self` to the left by `n` within its size, like `u32::rotate_left`.
+    /// Like there, `n` is taken modulo the number of bits.
+    pub fn rotate_left(self, n: u32) -> Self {
+        let size = self.size();
+        let bits = size.bits() as u32;
+        let n = n % bits;
+        if n == 0 {
+            return self;
+        }
+        let data = self.data;
+        Self { data: size.truncate(data << n | data >> (bits - n)), size: self.size }
+    }
+
+    /// Rotates the bits of `self` to the right by `n` within its size, like `u32::rotate_right`.
+    pub fn rotate_right(self, n: u32) -> Self {
+        let bits = self.size().bits() as u32;
+        self.rotate_left(bits - n % bits)
+    }
+
+    /// Raises `self` to the power of `exp`, wrapping around at the boundary of its size like
+    /// `u32::wrapping_pow`. The result doesn't depend on signedness, as wrapping only keeps the
+    /// low bits.
+    pub fn wrapping_pow(self, exp: u32) -> Self {
+        let size = self.size();
+        Self { data: size.truncate({ self.data }.wrapping_pow(exp)), size: self.size }
+    }
+
+    /// Negates `self`, wrapping around at the boundary of its size like `i32::wrapping_neg`. As
+    /// for `wrapping_pow`, the bits of the result are the same for signed and unsigned integers.
+    pub fn wrapping_neg(self) -> Self {
+        let size = self.size();
+        Self { data: size.truncate(0u128.wrapping_sub(self.data)), size: self.size }
+    }
+
+    /// Returns the smallest power of two greater than or equal to `self`, which is interpreted as
+    /// unsigned, like `u32::next_power_of_two`. If that power doesn't fit into the size of `self`,
+    /// the result wraps around to zero. This is what the library returns when overflow checks are
+    /// disabled, as they are when const-evaluating the library's own code.
+    pub fn next_power_of_two(self) -> Self {
+        let size = self.size();
+        let data = { self.data }
+            .checked_next_power_of_two()
+            .filter(|&power| power <= size.unsigned_int_max())
+            .unwrap_or(0);
+        Self { data, size: self.size }
+    }
+
+    /// Returns the size of the narrowest integer type that can hold `self`, which is interpreted
+    /// as signed if `signed` is set. For example, `255u16` fits into one byte, but `256u16` and
+    /// `128i16` need two.
+    pub fn minimal_int_size(self, signed: bool) -> Size {
+        let size = self.size();
+        let val = size.sign_extend(self.data) as i128;
+        [1, 2, 4, 8, 16]
+            .into_iter()
+            .map(Size::from_bytes)
+            .find(|candidate| {
+                if signed {
+                    (candidate.signed_int_min()..=candidate.signed_int_max()).contains(&val)
+                } else {
+                    self.data <= candidate.unsigned_int_max()
+                }
+            })
+            .unwrap_or(size)
+    }
+
+    /// Interprets `self` as an unsigned number of bytes and converts it to an alignment. Returns
+    /// `None` if that isn't a power of two or is too large. Unlike `Align::from_bytes`, this also
+    /// rejects zero, which isn't a valid alignment value.
+    pub fn try_to_align(self) -> Option<Align> {
+        let bytes = u64::try_from(self.data).ok()?;
+        if bytes == 0 {
+            return None;
+        }
+        Align::from_bytes(bytes).ok()
+    }
+
+    /// Reinterprets the bits of `self` as a value of `size` bytes, like `transmute` does for
+    /// scalars. This doesn't change the bits, but fails with `None` if the sizes differ.
+    pub fn try_transmute(self, size: Size) -> Option<Self> {
+        (self.size() == size).then_some(self)
+    }
+
+    /// Reverses the order of the bytes of `self` within its size, like `u32::swap_bytes`.
+    pub fn swap_bytes(self) -> Self {
+        let data = { self.data }.swap_bytes() >> (128 - self.size().bits());
+        Self { data, size: self.size }
+    }
+
+    /// Reverses the order of the bits of `self` within its size, like `u32::reverse_bits`. So
+    /// `1u8` becomes `0x80`, not `1 << 127`.
+    pub fn reverse_bits(self) -> Self {
+        let data = { self.data }.reverse_bits() >> (128 - self.size().bits());
+        Self { data, size: self.size }
+    }
+
+    /// Maps `a..=z` to `A..=Z` if `self` is an ASCII code point, like `u8::to_ascii_uppercase` and
+    /// `char::to_ascii_uppercase`. All other values are returned unchanged.
+    pub fn to_ascii_uppercase(self) -> Self {
+        self.map_ascii(u8::to_ascii_uppercase)
+    }
+
+    /// Maps `A..=Z` to `a..=z` if `self` is an ASCII code point, like `u8::to_ascii_lowercase` and
+    /// `char::to_ascii_lowercase`. All other values are returned unchanged.
+    pub fn to_ascii_lowercase(self) -> Self {
+        self.map_ascii(u8::to_ascii_lowercase)
+    }
+
+    fn map_ascii(self, f: impl FnOnce(&u8) -> u8) -> Self {
+        match u8::try_from(self.data) {
+            Ok(byte) if byte.is_ascii() => Self { data: f(&byte).into(), size: self.size },
+            _ => self,
+        }
+    }
+
+    /// Returns whether all bits set in `mask` are also set in `self`, like `flags.contains(mask)`
+    /// for a bitflags type. This is `false` if `mask` has bits set beyond the size of `self`.
+    pub fn contains_bits(self, mask: u128) -> bool {
+        self.data & mask == mask
+    }
+
+    /// Compares two integers of the same size, sign-extending them first if `signed` is set.
+    /// Returns `None` if the sizes differ.
+    pub fn cmp_int(self, other: Self, signed: bool) -> Option<Ordering> {
+        let size = self.size();
+        if other.size() != size {
+            return None;
+        }
+        Some(if signed {
+            (size.sign_extend(self.data) as i128).cmp(&(size.sign_extend(other.data) as i128))
+        } else {
+            { self.data }.cmp(&{ other.data })
+        })
+    }
+
+    /// Returns the larger of two `f32` or `f64` values of the same size, following IEEE maxNum
+    /// like `f32::max`: if one of them is NaN, the other one is returned. If the values compare
+    /// equal, e.g. for `0.0` and `-0.0`, `self` is returned. Returns `None` if the sizes differ or
+    /// aren't those of a float type.
+    pub fn float_max(self, other: Self) -> Option<Self> {
+        match (self.size().bytes(), other.size().bytes()) {
+            (4, 4) => Some(Float::max(Single::try_from(self).ok()?, other.try_into().ok()?).into()),
+            (8, 8) => Some(Float::max(Double::try_from(self).ok()?, other.try_into().ok()?).into()),
+            _ => None,
+        }
+    }
+
+    /// Like `float_max`, but returns the smaller value, following IEEE minNum.
+    pub fn float_min(self, other: Self) -> Option<Self> {
+        match (self.size().bytes(), other.size().bytes()) {
+            (4, 4) => Some(Float::min(Single::try_from(self).ok()?, other.try_into().ok()?).into()),
+            (8, 8) => Some(Float::min(Double::try_from(self).ok()?, other.try_into().ok()?).into()),
+            _ => None,
+        }
+    }
+
+    /// Returns `-1`, `0` or `1` depending on the sign of `self` interpreted as a signed integer,
+    /// like `i32::signum`.
+    pub fn signum_int(self) -> Self {
+        let size = self.size();
+        let signum = (size.sign_extend(self.data) as i128).signum();
+        Self::try_from_int(signum, size).unwrap()
+    }
+
+    /// Returns the sign of `self` interpreted as an `f32` or `f64`, like `f32::signum`: `1.0` for
+    /// positive values including `0.0`, `-1.0` for negative values including `-0.0`, and NaN for
+    /// NaN. Like the library, this returns the default NaN rather than the input's payload.
+    /// Returns `None` if the size isn't that of a float type.
+    pub fn float_signum(self) -> Option<Self> {
+        fn signum<F: Float>(f: F) -> F {
+            if f.is_nan() {
+                F::NAN
+            } else if f.is_negative() {
+                -F::from_u128(1).value
+            } else {
+                F::from_u128(1).value
+            }
+        }
+        match self.size().bytes() {
+            4 => Some(signum(Single::try_from(self).ok()?).into()),
+            8 => Some(signum(Double::try_from(self).ok()?).into()),
+            _ => None,
+        }
+    }
+
+    /// Converts `self`, interpreted as an integer that is signed if `signed` is set, to the nearest
+    /// `f64`, like an `as` cast does. This is lossy for integers with more than 53 significant
+    /// bits, e.g. `u64::MAX` becomes `2^64`.
+    pub fn int_to_f64_lossy(self, signed: bool) -> f64 {
+        let size = self.size();
+        if signed { size.sign_extend(self.data) as i128 as f64 } else { self.data as f64 }
+    }
+
+    /// Converts `self` interpreted as an `f32` or `f64` to an `f64`, which is exact. Returns
+    /// `None` if the size isn't that of a float type.
+    pub fn float_to_f64(self) -> Option<f64> {
+        let value: Double = match self.size().bytes() {
+            4 => Single::try_from(self).ok()?.convert(&mut false).value,
+            8 => Double::try_from(self).ok()?,
+            _ => return None,
+        };
+        Some(f64::from_bits(value.to_bits() as u64))
+    }
+
+    /// Classifies `self` interpreted as an `f32` or `f64`. Unlike converting it to a host float,
+    /// this keeps the sign and payload of NaNs. Returns `None` if the size isn't that of a float
+    /// type.
+    pub fn float_category(self) -> Option<FloatCategory> {
+        fn category<F: FloatConvert<Double>>(f: F) -> FloatCategory {
+            if f.is_nan() {
+                let quiet_bit = 1 << (F::PRECISION - 2);
+                return FloatCategory::Nan {
+                    negative: f.is_negative(),
+                    quiet: !f.is_signaling(),
+                    payload: (f.to_bits() & (quiet_bit - 1)) as u64,
+                };
+            }
+            if f.is_infinite() {
+                return FloatCategory::Infinite { negative: f.is_negative() };
+            }
+            // Widening to `f64` is exact for all other values.
+            let value: Double = f.convert(&mut false).value;
+            let value = f64::from_bits(value.to_bits() as u64);
+            if f.is_denormal() {
+                FloatCategory::Subnormal(value)
+            } else {
+                FloatCategory::Finite(value)
+            }
+        }
+        match self.size().bytes() {
+            4 => Some(category(Single::try_from(self).ok()?)),
+            8 => Some(category(Double::try_from(self).ok()?)),
+            _ => None,
+        }
+    }
+
+    /// Restricts `self` to the range `lo..=hi` of integers of the same size, sign-extending them
+    /// first if `signed` is set, like `i32::clamp`. Returns `None` if the sizes differ or if
+    /// `lo > hi`, where `clamp` panics.
+    pub fn clamp_int(self, lo: Self, hi: Self, signed: bool) -> Option<Self> {
+        if lo.cmp_int(hi, signed)?.is_gt() {
+            return None;
+        }
+        Some(if self.cmp_int(lo, signed)?.is_lt() {
+            lo
+        } else if self.cmp_int(hi, signed)?.is_gt() {
+            hi
+        } else {
+            self
+        })
+    }
+
+    /// Restricts `self` interpreted as an `f32` or `f64` to the range `lo..=hi`, like
+    /// `f32::clamp`. If `self` is NaN, it is returned unchanged. Returns `None` if the sizes differ
+    /// or aren't those of a float type, and, where `clamp` panics, if `lo > hi` or either bound is
+    /// NaN.
+    pub fn float_clamp(self, lo: Self, hi: Self) -> Option<Self> {
+        fn clamp<F: Float>(x: F, lo: F, hi: F) -> Option<F> {
+            if lo.is_nan() || hi.is_nan() || lo > hi {
+                return None;
+            }
+            // Comparisons with NaN are false, so NaN falls through.
+            Some(if x < lo {
+                lo
+            } else if x > hi {
+                hi
+            } else {
+                x
+            })
+        }
+        match (self.size().bytes(), lo.size().bytes(), hi.size().bytes()) {
+            (4, 4, 4) => Some(
+                clamp(Single::try_from(self).ok()?, lo.try_into().ok()?, hi.try_into().ok()?)?
+                    .into(),
+            ),
+            (8, 8, 8) => Some(
+                clamp(Double::try_from(self).ok()?, lo.try_into().ok()?, hi.try_into().ok()?)?
+                    .into(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Computes the absolute value of `self` interpreted as a float by clearing its sign bit.
+    /// Like `f32::abs`, this turns `-0.0` into `0.0` and keeps the payload of NaNs.
+    pub fn float_abs(self) -> Self {
+        let sign_bit = 1u128 << (self.size().bits() - 1);
+        Self { data: self.data & !sign_bit, size: self.size }
+    }
 }
 
 macro_rules! from {
@@ -409,6 +803,25 @@ fn from(c: char) -> Self {
     }
 }
 
+/// What kind of value a float holds, as returned by `ScalarInt::float_category`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FloatCategory {
+    /// A normal number or zero.
+    Finite(f64),
+    /// A non-zero number that is smaller than the smallest normal number of its type.
+    Subnormal(f64),
+    Infinite {
+        negative: bool,
+    },
+    /// A NaN. `payload` holds the bits of the significand other than the quiet bit, so a
+    /// signaling NaN always has a non-zero payload.
+    Nan {
+        negative: bool,
+        quiet: bool,
+        payload: u64,
+    },
+}
+
 /// Error returned when a conversion from ScalarInt to char fails.
 #[derive(Debug)]
 pub struct CharTryFromScalarInt;
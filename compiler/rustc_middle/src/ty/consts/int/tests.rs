@@ -0,0 +1,501 @@
+use super::*;
+
+#[test]
+fn try_to_int_sign_extends() {
+    let size = Size::from_bytes(1);
+    let int = ScalarInt::try_from_int(-1i8, size).unwrap();
+    assert_eq!(int.try_to_int(size), Ok(-1));
+
+    let size = Size::from_bytes(2);
+    let int = ScalarInt::try_from_int(i16::MIN, size).unwrap();
+    assert_eq!(int.try_to_int(size), Ok(i16::MIN.into()));
+}
+
+#[test]
+fn try_to_int_beyond_i64() {
+    let size = Size::from_bytes(16);
+    let val = i128::from(i64::MIN) - 1;
+    let int = ScalarInt::try_from_int(val, size).unwrap();
+    assert_eq!(int.try_to_int(size), Ok(val));
+    assert!(i64::try_from(int.try_to_int(size).unwrap()).is_err());
+}
+
+#[test]
+fn checked_abs_int() {
+    let int = ScalarInt::try_from_int(-5i32, Size::from_bytes(4)).unwrap();
+    assert_eq!(int.checked_abs_int().unwrap().try_to_i32(), Ok(5));
+
+    let int = ScalarInt::try_from_int(i32::MIN, Size::from_bytes(4)).unwrap();
+    assert_eq!(int.checked_abs_int(), None);
+
+    let int = ScalarInt::try_from_int(i128::MIN, Size::from_bytes(16)).unwrap();
+    assert_eq!(int.checked_abs_int(), None);
+}
+
+#[test]
+fn float_abs() {
+    let int = ScalarInt::from((-0.0f32).to_bits());
+    assert_eq!(int.float_abs(), ScalarInt::from(0.0f32.to_bits()));
+
+    let int = ScalarInt::from((-1.5f64).to_bits());
+    assert_eq!(int.float_abs(), ScalarInt::from(1.5f64.to_bits()));
+}
+
+#[test]
+fn try_to_align_validates() {
+    assert_eq!(ScalarInt::from(8u64).try_to_align(), Some(Align::from_bytes(8).unwrap()));
+    assert_eq!(ScalarInt::from(1u8).try_to_align(), Some(Align::ONE));
+    assert_eq!(
+        ScalarInt::from(1u32 << 29).try_to_align(),
+        Some(Align::from_bytes(1 << 29).unwrap())
+    );
+    assert_eq!(ScalarInt::from(3u64).try_to_align(), None);
+    assert_eq!(ScalarInt::from(0u64).try_to_align(), None);
+    assert_eq!(ScalarInt::from(1u32 << 30).try_to_align(), None);
+    assert_eq!(ScalarInt::from(1u128 << 64).try_to_align(), None);
+}
+
+#[test]
+fn try_transmute_keeps_bits() {
+    let float = ScalarInt::from(1.5f32.to_bits());
+    let int = float.try_transmute(Size::from_bytes(4)).unwrap();
+    assert_eq!(int.try_to_u32(), Ok(1.5f32.to_bits()));
+    let back = int.try_transmute(Size::from_bytes(4)).unwrap();
+    assert_eq!(Single::try_from(back).unwrap().to_bits(), 1.5f32.to_bits().into());
+
+    let wide = ScalarInt::try_from_int(-1i64, Size::from_bytes(8)).unwrap();
+    assert_eq!(wide.try_transmute(Size::from_bytes(4)), None);
+    assert_eq!(wide.try_transmute(Size::from_bytes(16)), None);
+}
+
+#[test]
+fn contains_bits() {
+    let flags = ScalarInt::from(0b1011u8);
+    assert!(flags.contains_bits(0b1011));
+    assert!(flags.contains_bits(0b0011));
+    assert!(flags.contains_bits(0));
+    assert!(!flags.contains_bits(0b0111));
+    assert!(!flags.contains_bits(0b1111));
+    assert!(!flags.contains_bits(0x100));
+
+    let all = ScalarInt::from(u128::MAX);
+    assert!(all.contains_bits(u128::MAX));
+}
+
+#[test]
+fn signum_int() {
+    let int = ScalarInt::try_from_int(0i32, Size::from_bytes(4)).unwrap();
+    assert_eq!(int.signum_int().try_to_i32(), Ok(0));
+
+    let int = ScalarInt::try_from_int(-5i8, Size::from_bytes(1)).unwrap();
+    assert_eq!(int.signum_int().try_to_i8(), Ok(-1));
+
+    let int = ScalarInt::try_from_int(i128::MAX, Size::from_bytes(16)).unwrap();
+    assert_eq!(int.signum_int().try_to_i128(), Ok(1));
+}
+
+#[test]
+fn float_signum() {
+    let signum = |f: f32| ScalarInt::from(f.to_bits()).float_signum().unwrap();
+    assert_eq!(signum(0.0), ScalarInt::from(1.0f32.to_bits()));
+    assert_eq!(signum(-0.0), ScalarInt::from((-1.0f32).to_bits()));
+    assert_eq!(signum(-7.5), ScalarInt::from((-1.0f32).to_bits()));
+    assert_eq!(signum(f32::INFINITY), ScalarInt::from(1.0f32.to_bits()));
+    assert_eq!(signum(f32::NAN), ScalarInt::from(f32::NAN.to_bits()));
+    assert_eq!(signum(f32::from_bits(0xffc0_0001)), ScalarInt::from(f32::NAN.to_bits()));
+
+    let int = ScalarInt::from((-0.0f64).to_bits());
+    assert_eq!(int.float_signum(), Some(ScalarInt::from((-1.0f64).to_bits())));
+    assert_eq!(ScalarInt::from(1u16).float_signum(), None);
+}
+
+#[test]
+fn overflowing_int_op_matches_host() {
+    let ops = [BinOp::Add, BinOp::Sub, BinOp::Mul];
+
+    let vals = [i8::MIN, i8::MIN + 1, -1, 0, 1, i8::MAX - 1, i8::MAX];
+    let size = Size::from_bytes(1);
+    for op in ops {
+        for l in vals {
+            for r in vals {
+                let expected = match op {
+                    BinOp::Add => l.overflowing_add(r),
+                    BinOp::Sub => l.overflowing_sub(r),
+                    _ => l.overflowing_mul(r),
+                };
+                let l = ScalarInt::try_from_int(l, size).unwrap();
+                let r = ScalarInt::try_from_int(r, size).unwrap();
+                let (res, overflow) = l.overflowing_int_op(op, r, true).unwrap();
+                assert_eq!((res.try_to_i8().unwrap(), overflow), expected);
+            }
+        }
+    }
+
+    let vals = [0, 1, 2, u32::MAX / 2, u32::MAX - 1, u32::MAX];
+    for op in ops {
+        for l in vals {
+            for r in vals {
+                let expected = match op {
+                    BinOp::Add => l.overflowing_add(r),
+                    BinOp::Sub => l.overflowing_sub(r),
+                    _ => l.overflowing_mul(r),
+                };
+                let (res, overflow) =
+                    ScalarInt::from(l).overflowing_int_op(op, ScalarInt::from(r), false).unwrap();
+                assert_eq!((res.try_to_u32().unwrap(), overflow), expected);
+            }
+        }
+    }
+
+    let vals = [i128::MIN, -1, 0, 1, i128::MAX];
+    let size = Size::from_bytes(16);
+    for op in ops {
+        for l in vals {
+            for r in vals {
+                let expected = match op {
+                    BinOp::Add => l.overflowing_add(r),
+                    BinOp::Sub => l.overflowing_sub(r),
+                    _ => l.overflowing_mul(r),
+                };
+                let l = ScalarInt::try_from_int(l, size).unwrap();
+                let r = ScalarInt::try_from_int(r, size).unwrap();
+                let (res, overflow) = l.overflowing_int_op(op, r, true).unwrap();
+                assert_eq!((res.try_to_i128().unwrap(), overflow), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn overflowing_int_op_rejects_mismatches() {
+    let (l, r) = (ScalarInt::from(1u8), ScalarInt::from(1u16));
+    assert_eq!(l.overflowing_int_op(BinOp::Add, r, false), None);
+    assert_eq!(l.overflowing_int_op(BinOp::Div, l, false), None);
+}
+
+#[test]
+fn cmp_int_respects_signedness() {
+    let size = Size::from_bytes(1);
+    let minus_one = ScalarInt::try_from_int(-1, size).unwrap();
+    let one = ScalarInt::try_from_int(1, size).unwrap();
+    assert_eq!(minus_one.cmp_int(one, true), Some(Ordering::Less));
+    assert_eq!(minus_one.cmp_int(one, false), Some(Ordering::Greater));
+    assert_eq!(one.cmp_int(one, true), Some(Ordering::Equal));
+    assert_eq!(one.cmp_int(ScalarInt::from(1u16), true), None);
+}
+
+#[test]
+fn clamp_int_at_and_beyond_bounds() {
+    let size = Size::from_bytes(1);
+    let i = |v: i8| ScalarInt::try_from_int(v, size).unwrap();
+    let (lo, hi) = (i(-10), i(20));
+    assert_eq!(i(-11).clamp_int(lo, hi, true), Some(lo));
+    assert_eq!(i(-10).clamp_int(lo, hi, true), Some(lo));
+    assert_eq!(i(5).clamp_int(lo, hi, true), Some(i(5)));
+    assert_eq!(i(20).clamp_int(lo, hi, true), Some(hi));
+    assert_eq!(i(i8::MAX).clamp_int(lo, hi, true), Some(hi));
+    assert_eq!(i(i8::MIN).clamp_int(lo, hi, true), Some(lo));
+
+    // `-10` is a large number when unsigned, so the bounds are reversed.
+    assert_eq!(i(5).clamp_int(lo, hi, false), None);
+    assert_eq!(i(5).clamp_int(hi, lo, true), None);
+    assert_eq!(i(5).clamp_int(i(5), i(5), true), Some(i(5)));
+    assert_eq!(ScalarInt::from(5u16).clamp_int(lo, hi, true), None);
+}
+
+#[test]
+fn float_clamp_at_and_beyond_bounds() {
+    let f = |v: f64| ScalarInt::from(v.to_bits());
+    let (lo, hi) = (f(-1.5), f(2.0));
+    assert_eq!(f(-2.0).float_clamp(lo, hi), Some(lo));
+    assert_eq!(f(-1.5).float_clamp(lo, hi), Some(lo));
+    assert_eq!(f(0.5).float_clamp(lo, hi), Some(f(0.5)));
+    assert_eq!(f(2.0).float_clamp(lo, hi), Some(hi));
+    assert_eq!(f(f64::INFINITY).float_clamp(lo, hi), Some(hi));
+    assert_eq!(f(f64::NEG_INFINITY).float_clamp(lo, hi), Some(lo));
+    // Zeros compare equal, so the value is kept.
+    assert_eq!(f(-0.0).float_clamp(f(0.0), hi), Some(f(-0.0)));
+
+    let nan = f(f64::NAN);
+    assert_eq!(nan.float_clamp(lo, hi), Some(nan));
+    assert_eq!(f(0.5).float_clamp(nan, hi), None);
+    assert_eq!(f(0.5).float_clamp(lo, nan), None);
+    assert_eq!(f(0.5).float_clamp(hi, lo), None);
+    assert_eq!(ScalarInt::from(0.5f32.to_bits()).float_clamp(lo, hi), None);
+}
+
+#[test]
+fn float_min_max_ignore_nan() {
+    let f = |v: f32| ScalarInt::from(Single::from_bits(v.to_bits().into()));
+    let nan = f(f32::NAN);
+    assert_eq!(f(1.0).float_max(f(-2.0)), Some(f(1.0)));
+    assert_eq!(f(1.0).float_min(f(-2.0)), Some(f(-2.0)));
+    assert_eq!(nan.float_max(f(1.0)), Some(f(1.0)));
+    assert_eq!(f(1.0).float_min(nan), Some(f(1.0)));
+    assert_eq!(nan.float_max(nan), Some(nan));
+    // Zeros compare equal, so the first argument wins.
+    assert_eq!(f(-0.0).float_max(f(0.0)), Some(f(-0.0)));
+
+    let d = |v: f64| ScalarInt::from(Double::from_bits(v.to_bits().into()));
+    assert_eq!(d(f64::NAN).float_min(d(-3.5)), Some(d(-3.5)));
+    assert_eq!(d(1.0).float_max(f(1.0)), None);
+    assert_eq!(ScalarInt::from(1u16).float_max(ScalarInt::from(2u16)), None);
+}
+
+#[test]
+fn bit_counts_use_size() {
+    let zero = ScalarInt::from(0u8);
+    assert_eq!(zero.count_ones(), 0);
+    assert_eq!(zero.count_zeros(), 8);
+    assert_eq!(zero.leading_zeros(), 8);
+    assert_eq!(zero.trailing_zeros(), 8);
+
+    let max = ScalarInt::from(u16::MAX);
+    assert_eq!(max.count_ones(), 16);
+    assert_eq!(max.count_zeros(), 0);
+    assert_eq!(max.leading_zeros(), 0);
+    assert_eq!(max.trailing_zeros(), 0);
+
+    let min = ScalarInt::try_from_int(i32::MIN, Size::from_bytes(4)).unwrap();
+    assert_eq!(min.count_ones(), i32::MIN.count_ones());
+    assert_eq!(min.count_zeros(), i32::MIN.count_zeros());
+    assert_eq!(min.leading_zeros(), i32::MIN.leading_zeros());
+    assert_eq!(min.trailing_zeros(), i32::MIN.trailing_zeros());
+
+    let one = ScalarInt::from(1u128);
+    assert_eq!(one.count_zeros(), 127);
+    assert_eq!(one.leading_zeros(), 127);
+    assert_eq!(one.trailing_zeros(), 0);
+    assert_eq!(ScalarInt::from(0u128).trailing_zeros(), 128);
+}
+
+#[test]
+fn rotate_within_size() {
+    for v in [0u8, 1, 0b1001_0110, 0x80, u8::MAX] {
+        for n in [0, 1, 3, 7, 8, 9, 17, 100, u32::MAX] {
+            let int = ScalarInt::from(v);
+            assert_eq!(int.rotate_left(n), ScalarInt::from(v.rotate_left(n)));
+            assert_eq!(int.rotate_right(n), ScalarInt::from(v.rotate_right(n)));
+        }
+    }
+    let v = 0x8000_0000_0000_0000_0000_0000_0000_0001u128;
+    assert_eq!(ScalarInt::from(v).rotate_left(129), ScalarInt::from(v.rotate_left(129)));
+    assert_eq!(ScalarInt::from(v).rotate_right(130), ScalarInt::from(v.rotate_right(130)));
+}
+
+#[test]
+fn swap_bytes_within_size() {
+    for v in [0u32, 1, 0x1234_5678, u32::MAX, 0x8000_0001] {
+        assert_eq!(ScalarInt::from(v).swap_bytes(), ScalarInt::from(v.swap_bytes()));
+    }
+    for v in [0u16, 1, 0x12ff, u16::MAX] {
+        assert_eq!(ScalarInt::from(v).swap_bytes(), ScalarInt::from(v.swap_bytes()));
+    }
+    assert_eq!(ScalarInt::from(0xabu8).swap_bytes(), ScalarInt::from(0xabu8));
+}
+
+#[test]
+fn reverse_bits_within_size() {
+    assert_eq!(ScalarInt::from(1u8).reverse_bits(), ScalarInt::from(0x80u8));
+    for v in [0u8, 0x0f, 0xa5, u8::MAX] {
+        assert_eq!(ScalarInt::from(v).reverse_bits(), ScalarInt::from(v.reverse_bits()));
+    }
+    for v in [0u16, 1, 0x12ff, 0x8000, u16::MAX] {
+        assert_eq!(ScalarInt::from(v).reverse_bits(), ScalarInt::from(v.reverse_bits()));
+    }
+    for v in [0u32, 1, 0x1234_5678, u32::MAX, 0x8000_0001] {
+        assert_eq!(ScalarInt::from(v).reverse_bits(), ScalarInt::from(v.reverse_bits()));
+    }
+}
+
+#[test]
+fn ascii_case_conversions() {
+    assert_eq!(ScalarInt::from(b'a').to_ascii_uppercase(), ScalarInt::from(b'A'));
+    assert_eq!(ScalarInt::from(b'a').to_ascii_lowercase(), ScalarInt::from(b'a'));
+    assert_eq!(ScalarInt::from(b'Z').to_ascii_lowercase(), ScalarInt::from(b'z'));
+    assert_eq!(ScalarInt::from(b'Z').to_ascii_uppercase(), ScalarInt::from(b'Z'));
+    assert_eq!(ScalarInt::from(b'0').to_ascii_uppercase(), ScalarInt::from(b'0'));
+    // Non-ASCII bytes are left alone, even if they are letters in Latin-1.
+    assert_eq!(ScalarInt::from(0xe9u8).to_ascii_uppercase(), ScalarInt::from(0xe9u8));
+    assert_eq!(ScalarInt::from(0xc9u8).to_ascii_lowercase(), ScalarInt::from(0xc9u8));
+
+    assert_eq!(ScalarInt::from('q').to_ascii_uppercase(), ScalarInt::from('Q'));
+    assert_eq!(ScalarInt::from('é').to_ascii_uppercase(), ScalarInt::from('é'));
+    assert_eq!(ScalarInt::from('é').to_ascii_uppercase().size(), Size::from_bytes(4));
+}
+
+#[test]
+fn wrapping_ops_at_type_boundaries() {
+    macro_rules! check {
+        ($($ty:ident),*) => {$({
+            let size = Size::from_bytes(std::mem::size_of::<$ty>());
+            let signed = $ty::MIN != 0;
+            let int = |v: $ty| ScalarInt::try_from_uint(v as u128 & size.unsigned_int_max(), size).unwrap();
+            for l in [$ty::MIN, $ty::MIN + 1, 0, 1, $ty::MAX - 1, $ty::MAX] {
+                assert_eq!(int(l).wrapping_neg(), int(l.wrapping_neg()));
+                for r in [$ty::MIN, 0, 1, 2, $ty::MAX] {
+                    let wrapping = |op| int(l).overflowing_int_op(op, int(r), signed).unwrap().0;
+                    assert_eq!(wrapping(BinOp::Add), int(l.wrapping_add(r)));
+                    assert_eq!(wrapping(BinOp::Sub), int(l.wrapping_sub(r)));
+                    assert_eq!(wrapping(BinOp::Mul), int(l.wrapping_mul(r)));
+                }
+            }
+        })*};
+    }
+    check!(u8, i8, u16, i16, u64, i64, u128, i128);
+}
+
+#[test]
+fn next_power_of_two_wraps_on_overflow() {
+    macro_rules! check {
+        ($($ty:ident),*) => {$({
+            let high = $ty::MAX / 2 + 1;
+            for v in [0, 1, 2, 3, 5, 64, 65, high - 1, high, high + 1, $ty::MAX] {
+                let expected = v.checked_next_power_of_two().unwrap_or(0);
+                assert_eq!(ScalarInt::from(v).next_power_of_two(), ScalarInt::from(expected));
+            }
+        })*};
+    }
+    check!(u8, u16, u32, u64, u128);
+}
+
+#[test]
+fn wrapping_pow_at_overflow_boundary() {
+    for (base, exp) in [(2u8, 7), (2, 8), (3, 5), (3, 6), (0, 0), (u8::MAX, 2)] {
+        assert_eq!(
+            ScalarInt::from(base).wrapping_pow(exp),
+            ScalarInt::from(base.wrapping_pow(exp))
+        );
+    }
+    let size = Size::from_bytes(1);
+    for (base, exp) in [(2i8, 6), (2, 7), (-2, 7), (-2, 8), (3, 5), (-1, u32::MAX)] {
+        let int = ScalarInt::try_from_int(base, size).unwrap();
+        assert_eq!(int.wrapping_pow(exp).try_to_i8().unwrap(), base.wrapping_pow(exp));
+    }
+    for (base, exp) in [(2u64, 63), (2, 64), (10, 19), (10, 20)] {
+        assert_eq!(
+            ScalarInt::from(base).wrapping_pow(exp),
+            ScalarInt::from(base.wrapping_pow(exp))
+        );
+    }
+}
+
+#[test]
+fn float_category_of_each_kind() {
+    let cat = |f: f64| ScalarInt::from(f.to_bits()).float_category().unwrap();
+    assert_eq!(cat(1.5), FloatCategory::Finite(1.5));
+    assert_eq!(cat(-0.0), FloatCategory::Finite(-0.0));
+    assert_eq!(cat(f64::from_bits(1)), FloatCategory::Subnormal(f64::from_bits(1)));
+    assert_eq!(cat(f64::NEG_INFINITY), FloatCategory::Infinite { negative: true });
+    assert_eq!(cat(f64::NAN), FloatCategory::Nan { negative: false, quiet: true, payload: 0 });
+    assert_eq!(
+        cat(f64::from_bits(0xfff8_0000_0000_0005)),
+        FloatCategory::Nan { negative: true, quiet: true, payload: 5 }
+    );
+    assert_eq!(
+        cat(f64::from_bits(0x7ff0_0000_0000_0001)),
+        FloatCategory::Nan { negative: false, quiet: false, payload: 1 }
+    );
+
+    let cat = |bits: u32| ScalarInt::from(bits).float_category().unwrap();
+    assert_eq!(cat(2.5f32.to_bits()), FloatCategory::Finite(2.5));
+    assert_eq!(cat(1), FloatCategory::Subnormal(f32::from_bits(1).into()));
+    assert_eq!(cat(f32::INFINITY.to_bits()), FloatCategory::Infinite { negative: false });
+    assert_eq!(cat(0x7f80_0003), FloatCategory::Nan { negative: false, quiet: false, payload: 3 });
+    assert_eq!(cat(0x7fc0_0000), FloatCategory::Nan { negative: false, quiet: true, payload: 0 });
+
+    assert_eq!(ScalarInt::from(1u16).float_category(), None);
+}
+
+#[test]
+fn minimal_int_size_at_boundaries() {
+    let minimal = |v: u128| ScalarInt::from(v).minimal_int_size(false).bytes();
+    assert_eq!(minimal(0), 1);
+    assert_eq!(minimal(255), 1);
+    assert_eq!(minimal(256), 2);
+    assert_eq!(minimal(u16::MAX.into()), 2);
+    assert_eq!(minimal(u32::MAX.into()), 4);
+    assert_eq!(minimal(u64::MAX.into()), 8);
+    assert_eq!(minimal(u64::MAX as u128 + 1), 16);
+
+    let size = Size::from_bytes(4);
+    let minimal = |v: i32| ScalarInt::try_from_int(v, size).unwrap().minimal_int_size(true).bytes();
+    assert_eq!(minimal(127), 1);
+    assert_eq!(minimal(128), 2);
+    assert_eq!(minimal(-128), 1);
+    assert_eq!(minimal(-129), 2);
+    assert_eq!(minimal(i32::MIN), 4);
+    // Interpreted as unsigned, `-1` is the maximum value.
+    assert_eq!(ScalarInt::try_from_int(-1, size).unwrap().minimal_int_size(false).bytes(), 4);
+}
+
+#[test]
+fn sum_wraps_at_overflow_boundary() {
+    let size = Size::from_bytes(1);
+    let ints = |vals: &[i8]| {
+        vals.iter().map(|&v| ScalarInt::try_from_int(v, size).unwrap()).collect::<Vec<_>>()
+    };
+    let sum = |vals: &[i8]| ScalarInt::sum(ints(vals), size, false).unwrap().try_to_i8().unwrap();
+    assert_eq!(sum(&[]), 0);
+    assert_eq!(sum(&[i8::MAX - 1, 1]), i8::MAX);
+    assert_eq!(sum(&[i8::MAX, 1]), i8::MIN);
+    assert_eq!(sum(&[i8::MIN, -1]), i8::MAX);
+    assert_eq!(sum(&[i8::MAX, 1, -1]), i8::MAX);
+
+    let sum =
+        |vals: &[u32]| ScalarInt::sum(vals.iter().map(|&v| v.into()), Size::from_bytes(4), false);
+    assert_eq!(sum(&[u32::MAX, 1]), Some(ScalarInt::from(0u32)));
+    assert_eq!(sum(&[u32::MAX, u32::MAX]), Some(ScalarInt::from(u32::MAX - 1)));
+
+    // All elements must have the given size.
+    assert_eq!(ScalarInt::sum([ScalarInt::from(1u16)], size, false), None);
+}
+
+#[test]
+fn sum_of_floats_matches_host() {
+    let sum = |vals: &[f64]| {
+        let sum =
+            ScalarInt::sum(vals.iter().map(|v| v.to_bits().into()), Size::from_bytes(8), true);
+        f64::from_bits(sum.unwrap().try_to_u64().unwrap())
+    };
+    for vals in [&[][..], &[0.1, 0.2, 0.3], &[1e308, 1e308], &[f64::INFINITY, -1.0], &[-0.0]] {
+        assert_eq!(sum(vals).to_bits(), vals.iter().sum::<f64>().to_bits());
+    }
+    assert!(sum(&[f64::INFINITY, f64::NEG_INFINITY]).is_nan());
+
+    let f32s = [0.1f32, 0.7, 1e-8];
+    let sum = ScalarInt::sum(f32s.iter().map(|v| v.to_bits().into()), Size::from_bytes(4), true);
+    assert_eq!(sum.unwrap().try_to_u32().unwrap(), f32s.iter().sum::<f32>().to_bits());
+
+    assert_eq!(ScalarInt::sum([ScalarInt::from(1u16)], Size::from_bytes(2), true), None);
+}
+
+#[test]
+fn to_f64_rounds_large_ints() {
+    assert_eq!(ScalarInt::from(u64::MAX).int_to_f64_lossy(false), u64::MAX as f64);
+    assert_eq!(ScalarInt::from(u64::MAX).int_to_f64_lossy(false), 18446744073709551616.0);
+    assert_eq!(ScalarInt::from(u64::MAX).int_to_f64_lossy(true), -1.0);
+    assert_eq!(ScalarInt::from(1u64 << 53).int_to_f64_lossy(false), 9007199254740992.0);
+    assert_eq!(ScalarInt::from(i128::MAX as u128).int_to_f64_lossy(true), i128::MAX as f64);
+    assert_eq!(ScalarInt::from(i128::MAX as u128).int_to_f64_lossy(true), 2f64.powi(127));
+    assert_eq!(ScalarInt::from(i128::MIN as u128).int_to_f64_lossy(true), -(2f64.powi(127)));
+    assert_eq!(ScalarInt::from(u128::MAX).int_to_f64_lossy(false), u128::MAX as f64);
+    assert_eq!(ScalarInt::from(-5i8 as u8).int_to_f64_lossy(true), -5.0);
+}
+
+#[test]
+fn float_to_f64_is_exact() {
+    let f32_to_f64 = |f: f32| ScalarInt::from(f.to_bits()).float_to_f64().unwrap();
+    assert_eq!(f32_to_f64(0.1), 0.1f32 as f64);
+    assert_ne!(f32_to_f64(0.1), 0.1);
+    assert_eq!(f32_to_f64(f32::MAX), f32::MAX as f64);
+    assert_eq!(f32_to_f64(-0.0).to_bits(), (-0.0f64).to_bits());
+    assert_eq!(f32_to_f64(f32::NEG_INFINITY), f64::NEG_INFINITY);
+    assert!(f32_to_f64(f32::NAN).is_nan());
+
+    let f64_to_f64 = |f: f64| ScalarInt::from(f.to_bits()).float_to_f64().unwrap();
+    assert_eq!(f64_to_f64(0.1), 0.1);
+    assert_eq!(f64_to_f64(f64::MIN_POSITIVE / 2.0), f64::MIN_POSITIVE / 2.0);
+
+    assert_eq!(ScalarInt::from(1u16).float_to_f64(), None);
+}
@@ -2,6 +2,11 @@
 use crate::mir::interpret::{AllocId, Scalar};
 use crate::ty::{self, Ty, TyCtxt};
 use rustc_macros::{HashStable, TyDecodable, TyEncodable};
+use rustc_target::abi::VariantIdx;
+use std::iter;
+
+#[cfg(test)]
+mod tests;
 
 #[derive(Copy, Clone, Debug, Hash, TyEncodable, TyDecodable, Eq, PartialEq, Ord, PartialOrd)]
 #[derive(HashStable)]
@@ -78,6 +83,142 @@ pub fn try_to_scalar_int(self) -> Option<ScalarInt> {
         }
     }
 
+    /// Returns the field or element at position `idx` of a branch, or `None` if `self` is a leaf
+    /// or `idx` is out of bounds.
+    pub fn try_get_element(self, idx: u64) -> Option<Self> {
+        match self {
+            Self::Branch(elems) => elems.get(usize::try_from(idx).ok()?).copied(),
+            Self::Leaf(_) => None,
+        }
+    }
+
+    /// Returns the first element of a branch, which is `None` if the branch is empty, or `None` if
+    /// `self` is a leaf.
+    pub fn try_first_element(self) -> Option<Option<Self>> {
+        match self {
+            Self::Branch(elems) => Some(elems.first().copied()),
+            Self::Leaf(_) => None,
+        }
+    }
+
+    /// Like `try_first_element`, but returns the last element.
+    pub fn try_last_element(self) -> Option<Option<Self>> {
+        match self {
+            Self::Branch(elems) => Some(elems.last().copied()),
+            Self::Leaf(_) => None,
+        }
+    }
+
+    /// Decodes the value of an enum, which is a branch of the variant index followed by the fields
+    /// of that variant. Returns `None` if `self` doesn't have that shape.
+    pub fn try_to_variant(self) -> Option<(VariantIdx, &'tcx [Self])> {
+        let Self::Branch([Self::Leaf(variant), fields @ ..]) = self else {
+            return None;
+        };
+        Some((VariantIdx::from_u32(variant.try_to_u32().ok()?), fields))
+    }
+
+    /// Returns the elements of the branch that represents the enum variant `variant` with the
+    /// values `fields`. This is the inverse of `try_to_variant`.
+    pub fn variant_branch_elems(variant: VariantIdx, fields: &[Self]) -> Vec<Self> {
+        iter::once(Self::Leaf(ScalarInt::from(variant.as_u32())))
+            .chain(fields.iter().copied())
+            .collect()
+    }
+
+    /// Returns the elements `start..end` of a branch as a new branch that shares `self`'s
+    /// storage. Returns `None` if `self` is a leaf or the range is out of bounds or reversed.
+    pub fn try_subslice(self, start: u64, end: u64) -> Option<Self> {
+        let Self::Branch(elems) = self else {
+            return None;
+        };
+        let range = usize::try_from(start).ok()?..usize::try_from(end).ok()?;
+        elems.get(range).map(Self::Branch)
+    }
+
+    /// Splits a branch into the elements before `mid` and the elements from `mid` on, like
+    /// `<[T]>::split_at`. Both halves share `self`'s storage. Returns `None` if `self` is a leaf or
+    /// `mid` is greater than the number of elements.
+    pub fn try_split_at(self, mid: u64) -> Option<(Self, Self)> {
+        let Self::Branch(elems) = self else {
+            return None;
+        };
+        let mid = usize::try_from(mid).ok().filter(|&mid| mid <= elems.len())?;
+        let (head, tail) = elems.split_at(mid);
+        Some((Self::Branch(head), Self::Branch(tail)))
+    }
+
+    /// Splits a branch into branches of `chunk_size` elements each, like `<[T]>::chunks`. The last
+    /// chunk is shorter if the number of elements isn't a multiple of `chunk_size`. The chunks
+    /// share `self`'s storage. Returns `None` if `self` is a leaf or `chunk_size` is zero.
+    pub fn try_chunks(self, chunk_size: u64) -> Option<Vec<Self>> {
+        let Self::Branch(elems) = self else {
+            return None;
+        };
+        let chunk_size = usize::try_from(chunk_size).ok().filter(|&size| size != 0)?;
+        Some(elems.chunks(chunk_size).map(Self::Branch).collect())
+    }
+
+    /// Returns all overlapping windows of `size` elements of a branch, like `<[T]>::windows`. The
+    /// windows share `self`'s storage. Returns `None` if `self` is a leaf, `size` is zero or `size`
+    /// is greater than the number of elements.
+    pub fn try_windows(self, size: u64) -> Option<Vec<Self>> {
+        let Self::Branch(elems) = self else {
+            return None;
+        };
+        let size = usize::try_from(size).ok().filter(|&size| size != 0 && size <= elems.len())?;
+        Some(elems.windows(size).map(Self::Branch).collect())
+    }
+
+    /// Returns whether any element of a branch is structurally equal to `needle`, or `None` if
+    /// `self` is a leaf.
+    pub fn branch_contains(self, needle: Self) -> Option<bool> {
+        let Self::Branch(elems) = self else {
+            return None;
+        };
+        Some(elems.contains(&needle))
+    }
+
+    /// Decodes a branch of `char` leaves, like the value of a `[char; N]` constant. Returns `None`
+    /// if `self` is a leaf or any element isn't a valid `char`.
+    pub fn try_to_chars(self) -> Option<Vec<char>> {
+        let Self::Branch(elems) = self else {
+            return None;
+        };
+        elems.iter().map(|elem| char::try_from(elem.try_to_scalar_int()?).ok()).collect()
+    }
+
+    /// Decodes a branch of `u8` leaves, like the value of a `(u8, u8, u8, u8)` constant. Returns
+    /// `None` if `self` is a leaf or any element isn't a `u8`.
+    pub fn try_to_u8s(self) -> Option<Vec<u8>> {
+        let Self::Branch(elems) = self else {
+            return None;
+        };
+        elems.iter().map(|elem| elem.try_to_scalar_int()?.try_to_u8().ok()).collect()
+    }
+
+    /// Decodes a branch of exactly two leaves, like the value of a `(usize, usize)` constant, and
+    /// returns their bits. Returns `None` for any other shape.
+    pub fn try_to_scalar_int_pair(self) -> Option<(u128, u128)> {
+        let Self::Branch(&[Self::Leaf(a), Self::Leaf(b)]) = self else {
+            return None;
+        };
+        Some((a.assert_bits(a.size()), b.assert_bits(b.size())))
+    }
+
+    /// Decodes the value of a `Range` or `RangeInclusive` constant over integers and returns the
+    /// bits of its start and end. The `exhausted` flag of a `RangeInclusive` is ignored. Returns
+    /// `None` for any other shape.
+    pub fn try_to_range_bounds(self) -> Option<(u128, u128)> {
+        match self {
+            Self::Branch(&[Self::Leaf(start), Self::Leaf(end)])
+            | Self::Branch(&[Self::Leaf(start), Self::Leaf(end), Self::Leaf(_)]) => {
+                Some((start.assert_bits(start.size()), end.assert_bits(end.size())))
+            }
+            _ => None,
+        }
+    }
+
     pub fn try_to_target_usize(self, tcx: TyCtxt<'tcx>) -> Option<u64> {
         self.try_to_scalar_int().and_then(|s| s.try_to_target_usize(tcx).ok())
     }
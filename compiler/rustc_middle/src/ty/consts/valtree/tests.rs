@@ -0,0 +1,246 @@
+use super::*;
+use rustc_data_structures::fx::FxHashSet;
+
+fn leaves(vals: &[u8]) -> Vec<ValTree<'static>> {
+    vals.iter().map(|&v| ValTree::Leaf(ScalarInt::from(v))).collect()
+}
+
+#[test]
+fn branches_compare_structurally() {
+    // Two branches built from separate allocations must be interchangeable as map keys.
+    let a = leaves(&[1, 2, 3]);
+    let b = leaves(&[1, 2, 3]);
+    let c = leaves(&[1, 2, 4]);
+    let (a, b, c) = (ValTree::Branch(&a), ValTree::Branch(&b), ValTree::Branch(&c));
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    let set: FxHashSet<_> = [a, b, c].into_iter().collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&ValTree::Branch(&leaves(&[1, 2, 3]))));
+}
+
+#[test]
+fn try_get_element_checks_bounds() {
+    let elems = leaves(&[7, 8, 9]);
+    let branch = ValTree::Branch(&elems);
+    assert_eq!(branch.try_get_element(0), Some(ValTree::Leaf(ScalarInt::from(7u8))));
+    assert_eq!(branch.try_get_element(2), Some(ValTree::Leaf(ScalarInt::from(9u8))));
+    assert_eq!(branch.try_get_element(3), None);
+    assert_eq!(branch.try_get_element(u64::MAX), None);
+
+    assert_eq!(ValTree::zst().try_get_element(0), None);
+    assert_eq!(ValTree::Leaf(ScalarInt::from(7u8)).try_get_element(0), None);
+}
+
+#[test]
+fn try_subslice_checks_bounds() {
+    let elems = leaves(&[1, 2, 3, 4]);
+    let branch = ValTree::Branch(&elems);
+    assert_eq!(branch.try_subslice(1, 3), Some(ValTree::Branch(&leaves(&[2, 3]))));
+    assert_eq!(branch.try_subslice(0, 4), Some(branch));
+    assert_eq!(branch.try_subslice(4, 4), Some(ValTree::zst()));
+    assert_eq!(branch.try_subslice(0, 0), Some(ValTree::zst()));
+    assert_eq!(branch.try_subslice(0, 5), None);
+    assert_eq!(branch.try_subslice(5, 5), None);
+    assert_eq!(branch.try_subslice(3, 2), None);
+    assert_eq!(branch.try_subslice(0, u64::MAX), None);
+
+    // The sub-slice shares the storage of the original branch.
+    let Some(ValTree::Branch(sub)) = branch.try_subslice(1, 3) else { unreachable!() };
+    assert!(std::ptr::eq(sub.as_ptr(), elems[1..].as_ptr()));
+
+    assert_eq!(ValTree::zst().try_subslice(0, 0), Some(ValTree::zst()));
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u8)).try_subslice(0, 0), None);
+}
+
+#[test]
+fn try_split_at_checks_bounds() {
+    let elems = leaves(&[1, 2, 3]);
+    let branch = ValTree::Branch(&elems);
+    assert_eq!(branch.try_split_at(0), Some((ValTree::zst(), branch)));
+    assert_eq!(branch.try_split_at(3), Some((branch, ValTree::zst())));
+    let (head, tail) = (leaves(&[1]), leaves(&[2, 3]));
+    assert_eq!(branch.try_split_at(1), Some((ValTree::Branch(&head), ValTree::Branch(&tail))));
+    assert_eq!(branch.try_split_at(4), None);
+    assert_eq!(branch.try_split_at(u64::MAX), None);
+
+    // Both halves share the storage of the original branch.
+    let Some((ValTree::Branch(head), ValTree::Branch(tail))) = branch.try_split_at(1) else {
+        unreachable!()
+    };
+    assert!(std::ptr::eq(head.as_ptr(), elems.as_ptr()));
+    assert!(std::ptr::eq(tail.as_ptr(), elems[1..].as_ptr()));
+
+    assert_eq!(ValTree::zst().try_split_at(0), Some((ValTree::zst(), ValTree::zst())));
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u8)).try_split_at(0), None);
+}
+
+#[test]
+fn try_chunks_splits_with_remainder() {
+    let elems = leaves(&[1, 2, 3, 4, 5, 6]);
+    let branch = ValTree::Branch(&elems);
+    let chunks = |size| branch.try_chunks(size).unwrap();
+    let (a, b, c) = (leaves(&[1, 2]), leaves(&[3, 4]), leaves(&[5, 6]));
+    assert_eq!(chunks(2), [ValTree::Branch(&a), ValTree::Branch(&b), ValTree::Branch(&c)]);
+    let (a, b) = (leaves(&[1, 2, 3, 4]), leaves(&[5, 6]));
+    assert_eq!(chunks(4), [ValTree::Branch(&a), ValTree::Branch(&b)]);
+    assert_eq!(chunks(6), [branch]);
+    assert_eq!(chunks(u64::MAX), [branch]);
+
+    // The chunks share the storage of the original branch.
+    let ValTree::Branch(last) = chunks(4)[1] else { unreachable!() };
+    assert!(std::ptr::eq(last.as_ptr(), elems[4..].as_ptr()));
+
+    assert_eq!(branch.try_chunks(0), None);
+    assert_eq!(ValTree::zst().try_chunks(2), Some(vec![]));
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u8)).try_chunks(1), None);
+}
+
+#[test]
+fn try_windows_overlaps() {
+    let elems = leaves(&[1, 2, 3, 4]);
+    let branch = ValTree::Branch(&elems);
+    let windows = |size| branch.try_windows(size).unwrap();
+    let (a, b, c) = (leaves(&[1, 2]), leaves(&[2, 3]), leaves(&[3, 4]));
+    assert_eq!(windows(2), [ValTree::Branch(&a), ValTree::Branch(&b), ValTree::Branch(&c)]);
+    assert_eq!(windows(1).len(), 4);
+    // A window as large as the branch is the whole branch.
+    assert_eq!(windows(4), [branch]);
+
+    // The windows share the storage of the original branch.
+    let ValTree::Branch(last) = windows(3)[1] else { unreachable!() };
+    assert!(std::ptr::eq(last.as_ptr(), elems[1..].as_ptr()));
+
+    assert_eq!(branch.try_windows(5), None);
+    assert_eq!(branch.try_windows(u64::MAX), None);
+    assert_eq!(branch.try_windows(0), None);
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u8)).try_windows(1), None);
+}
+
+#[test]
+fn branch_contains_finds_needles() {
+    let elems = leaves(&[3, 1, 4]);
+    let branch = ValTree::Branch(&elems);
+    assert_eq!(branch.branch_contains(ValTree::Leaf(ScalarInt::from(4u8))), Some(true));
+    assert_eq!(branch.branch_contains(ValTree::Leaf(ScalarInt::from(2u8))), Some(false));
+    // Leaves of a different size never match.
+    assert_eq!(branch.branch_contains(ValTree::Leaf(ScalarInt::from(4u16))), Some(false));
+
+    assert_eq!(ValTree::zst().branch_contains(ValTree::Leaf(ScalarInt::from(4u8))), Some(false));
+    assert_eq!(ValTree::Leaf(ScalarInt::from(4u8)).branch_contains(ValTree::zst()), None);
+}
+
+#[test]
+fn try_to_variant_splits_fields() {
+    // The values of `Some(7u8)` and `None::<u8>`.
+    let some = [ValTree::Leaf(ScalarInt::from(1u32)), ValTree::Leaf(ScalarInt::from(7u8))];
+    let none = [ValTree::Leaf(ScalarInt::from(0u32))];
+    assert_eq!(
+        ValTree::Branch(&some).try_to_variant(),
+        Some((VariantIdx::from_u32(1), &some[1..]))
+    );
+    assert_eq!(ValTree::Branch(&none).try_to_variant(), Some((VariantIdx::from_u32(0), &[][..])));
+
+    // The variant index is always a `u32`.
+    assert_eq!(ValTree::Branch(&leaves(&[1, 7])).try_to_variant(), None);
+    assert_eq!(ValTree::zst().try_to_variant(), None);
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u32)).try_to_variant(), None);
+}
+
+#[test]
+fn variant_branch_elems_round_trips() {
+    // The value of `Some(7u8)`, built from the field of an `Ok(7u8)`.
+    let ok = [ValTree::Leaf(ScalarInt::from(0u32)), ValTree::Leaf(ScalarInt::from(7u8))];
+    let (_, fields) = ValTree::Branch(&ok).try_to_variant().unwrap();
+    let some = ValTree::variant_branch_elems(VariantIdx::from_u32(1), fields);
+    assert_eq!(some, [ValTree::Leaf(ScalarInt::from(1u32)), ValTree::Leaf(ScalarInt::from(7u8))]);
+    assert_eq!(
+        ValTree::Branch(&some).try_to_variant(),
+        Some((VariantIdx::from_u32(1), &some[1..]))
+    );
+
+    // The value of `None`, as built for an `Err`.
+    let none = ValTree::variant_branch_elems(VariantIdx::from_u32(0), &[]);
+    assert_eq!(none, [ValTree::Leaf(ScalarInt::from(0u32))]);
+    assert_eq!(ValTree::Branch(&none).try_to_variant(), Some((VariantIdx::from_u32(0), &[][..])));
+}
+
+#[test]
+fn try_to_chars_decodes_elements() {
+    let chars: Vec<_> =
+        [',', '€', '\u{10FFFF}'].into_iter().map(|c| ValTree::Leaf(ScalarInt::from(c))).collect();
+    assert_eq!(ValTree::Branch(&chars).try_to_chars(), Some(vec![',', '€', '\u{10FFFF}']));
+    assert_eq!(ValTree::zst().try_to_chars(), Some(vec![]));
+
+    // A surrogate isn't a valid `char`.
+    let invalid = [ValTree::Leaf(ScalarInt::from(0xD800u32))];
+    assert_eq!(ValTree::Branch(&invalid).try_to_chars(), None);
+    assert_eq!(ValTree::Branch(&leaves(&[b'a'])).try_to_chars(), None);
+    assert_eq!(ValTree::Leaf(ScalarInt::from('a')).try_to_chars(), None);
+}
+
+#[test]
+fn try_to_u8s_reads_byte_tuples() {
+    // The value of `(127u8, 0u8, 0u8, 1u8)`.
+    let addr = leaves(&[127, 0, 0, 1]);
+    assert_eq!(ValTree::Branch(&addr).try_to_u8s(), Some(vec![127, 0, 0, 1]));
+    assert_eq!(ValTree::zst().try_to_u8s(), Some(vec![]));
+
+    let wide = [ValTree::Leaf(ScalarInt::from(127u8)), ValTree::Leaf(ScalarInt::from(1u16))];
+    assert_eq!(ValTree::Branch(&wide).try_to_u8s(), None);
+    let nested = [ValTree::Branch(&addr)];
+    assert_eq!(ValTree::Branch(&nested).try_to_u8s(), None);
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u8)).try_to_u8s(), None);
+}
+
+#[test]
+fn try_first_and_last_element() {
+    let arr = leaves(&[1, 2, 3]);
+    let arr = ValTree::Branch(&arr);
+    assert_eq!(arr.try_first_element(), Some(Some(ValTree::Leaf(ScalarInt::from(1u8)))));
+    assert_eq!(arr.try_last_element(), Some(Some(ValTree::Leaf(ScalarInt::from(3u8)))));
+
+    let single = leaves(&[7]);
+    let single = ValTree::Branch(&single);
+    assert_eq!(single.try_first_element(), single.try_last_element());
+
+    assert_eq!(ValTree::zst().try_first_element(), Some(None));
+    assert_eq!(ValTree::zst().try_last_element(), Some(None));
+
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u8)).try_first_element(), None);
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u8)).try_last_element(), None);
+}
+
+#[test]
+fn try_to_range_bounds() {
+    // `0..10`
+    let range = leaves(&[0, 10]);
+    assert_eq!(ValTree::Branch(&range).try_to_range_bounds(), Some((0, 10)));
+
+    // `5..=5`, which also stores whether it is exhausted.
+    let inclusive = [
+        ValTree::Leaf(ScalarInt::from(5u8)),
+        ValTree::Leaf(ScalarInt::from(5u8)),
+        ValTree::Leaf(ScalarInt::from(false)),
+    ];
+    assert_eq!(ValTree::Branch(&inclusive).try_to_range_bounds(), Some((5, 5)));
+
+    assert_eq!(ValTree::Branch(&leaves(&[1])).try_to_range_bounds(), None);
+    assert_eq!(ValTree::Branch(&leaves(&[1, 2, 3, 4])).try_to_range_bounds(), None);
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u8)).try_to_range_bounds(), None);
+}
+
+#[test]
+fn try_to_scalar_int_pair_needs_two_leaves() {
+    let pair = [ValTree::Leaf(ScalarInt::from(3u8)), ValTree::Leaf(ScalarInt::from(u64::MAX))];
+    assert_eq!(ValTree::Branch(&pair).try_to_scalar_int_pair(), Some((3, u64::MAX.into())));
+
+    assert_eq!(ValTree::Branch(&leaves(&[1])).try_to_scalar_int_pair(), None);
+    assert_eq!(ValTree::Branch(&leaves(&[1, 2, 3])).try_to_scalar_int_pair(), None);
+    assert_eq!(ValTree::zst().try_to_scalar_int_pair(), None);
+    assert_eq!(ValTree::Leaf(ScalarInt::from(1u8)).try_to_scalar_int_pair(), None);
+
+    let nested = [ValTree::Branch(&pair), ValTree::Leaf(ScalarInt::from(1u8))];
+    assert_eq!(ValTree::Branch(&nested).try_to_scalar_int_pair(), None);
+}
@@ -558,6 +558,18 @@ pub struct GlobalCtxt<'tcx> {
 
     /// Stores memory for globals (statics/consts).
     pub(crate) alloc_map: Lock<interpret::AllocMap<'tcx>>,
+
+    /// The values of all constants evaluated to a valtree so far, with their types. Only filled
+    /// with `-Z collect-const-values`, see the `collected_const_values` query.
+    pub evaluated_const_values: Lock<Vec<(interpret::GlobalId<'tcx>, Ty<'tcx>, ty::ValTree<'tcx>)>>,
+
+    /// Which constants forced the evaluation of which others. Only filled with
+    /// `-Z dump-const-deps`.
+    pub const_eval_deps: Lock<interpret::ConstEvalDeps<'tcx>>,
+
+    /// How often each anonymous or inline constant was evaluated, for different instances. Only
+    /// counted where the `repeated_inline_const_eval` lint is enabled.
+    pub inline_const_eval_counts: Lock<FxHashMap<DefId, usize>>,
 }
 
 impl<'tcx> GlobalCtxt<'tcx> {
@@ -706,6 +718,9 @@ pub fn create_global_ctxt(
             new_solver_evaluation_cache: Default::default(),
             data_layout,
             alloc_map: Lock::new(interpret::AllocMap::new()),
+            evaluated_const_values: Lock::new(Vec::new()),
+            const_eval_deps: Default::default(),
+            inline_const_eval_counts: Default::default(),
         }
     }
 
@@ -2399,4 +2414,6 @@ pub fn provide(providers: &mut Providers) {
         tcx.lang_items().panic_impl().is_some_and(|did| did.is_local())
     };
     providers.source_span = |tcx, def_id| tcx.untracked.source_span.get(def_id).unwrap_or(DUMMY_SP);
+    providers.collected_const_values =
+        |tcx, ()| tcx.arena.alloc_from_iter(tcx.evaluated_const_values.lock().iter().copied());
 }
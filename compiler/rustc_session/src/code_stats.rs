@@ -85,10 +85,31 @@ pub struct VTableSizeInfo {
     pub upcasting_cost_percent: f64,
 }
 
+pub struct ConstSizeInfo {
+    pub def_path: String,
+
+    /// Size of the constant's allocation and of all the allocations it points to.
+    pub size: u64,
+}
+
+#[derive(Default)]
+pub struct ValTreeStats {
+    /// Number of valtrees built.
+    pub count: usize,
+
+    /// Total number of nodes across all valtrees.
+    pub nodes: usize,
+
+    /// The path of the constant with the largest valtree and its number of nodes.
+    pub largest: Option<(String, usize)>,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
     vtable_sizes: Lock<FxHashMap<DefId, VTableSizeInfo>>,
+    const_sizes: Lock<Vec<ConstSizeInfo>>,
+    valtree_stats: Lock<ValTreeStats>,
 }
 
 impl CodeStats {
@@ -130,6 +151,21 @@ pub fn record_vtable_size(&self, trait_did: DefId, trait_name: &str, info: VTabl
         );
     }
 
+    pub fn record_const_size(&self, info: ConstSizeInfo) {
+        self.const_sizes.lock().push(info);
+    }
+
+    /// Records a valtree with `nodes` nodes. `def_path` is only called if this is the largest
+    /// valtree so far.
+    pub fn record_valtree(&self, nodes: usize, def_path: impl FnOnce() -> String) {
+        let mut stats = self.valtree_stats.lock();
+        stats.count += 1;
+        stats.nodes += nodes;
+        if stats.largest.as_ref().map_or(true, |&(_, largest)| nodes > largest) {
+            stats.largest = Some((def_path(), nodes));
+        }
+    }
+
     pub fn print_type_sizes(&self) {
         let type_sizes = self.type_sizes.borrow();
         let mut sorted: Vec<_> = type_sizes.iter().collect();
@@ -254,4 +290,25 @@ pub fn print_vtable_sizes(&self, crate_name: &str) {
             );
         }
     }
+
+    pub fn print_large_consts(&self) {
+        let mut infos = std::mem::take(&mut *self.const_sizes.lock());
+
+        // Primary sort: size in reverse order (from largest to smallest)
+        // Secondary sort: def_path
+        infos.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.def_path.cmp(&b.def_path)));
+
+        for ConstSizeInfo { def_path, size } in infos {
+            println!("print-large-consts {def_path}: {size} bytes");
+        }
+    }
+
+    pub fn print_valtree_stats(&self) {
+        let ValTreeStats { count, nodes, largest } = &*self.valtree_stats.lock();
+        println!("const-eval-stats valtrees: {count}");
+        println!("const-eval-stats nodes: {nodes}");
+        if let Some((def_path, nodes)) = largest {
+            println!("const-eval-stats largest: {def_path} ({nodes} nodes)");
+        }
+    }
 }
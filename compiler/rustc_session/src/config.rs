@@ -3122,6 +3122,7 @@ fn hash(
 
     impl_dep_tracking_hash_via_hash!(
         bool,
+        u8,
         usize,
         NonZeroUsize,
         u64,
@@ -358,6 +358,7 @@ mod desc {
     pub const parse_string_push: &str = parse_string;
     pub const parse_opt_langid: &str = "a language identifier";
     pub const parse_opt_pathbuf: &str = "a path";
+    pub const parse_opt_hex_bytes: &str = "a non-empty string of hexadecimal byte values";
     pub const parse_list: &str = "a space-separated list of strings";
     pub const parse_list_with_polarity: &str =
         "a comma-separated list of strings, with elements beginning with + or -";
@@ -514,6 +515,16 @@ pub(crate) fn parse_opt_pathbuf(slot: &mut Option<PathBuf>, v: Option<&str>) ->
         }
     }
 
+    pub(crate) fn parse_opt_hex_bytes(slot: &mut Option<Vec<u8>>, v: Option<&str>) -> bool {
+        let Some(s) = v else { return false };
+        if s.is_empty() || s.len() % 2 != 0 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return false;
+        }
+        let bytes = (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap());
+        *slot = Some(bytes.collect());
+        true
+    }
+
     pub(crate) fn parse_string_push(slot: &mut Vec<String>, v: Option<&str>) -> bool {
         match v {
             Some(s) => {
@@ -1417,8 +1428,29 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "instrument control-flow architecture protection"),
     codegen_backend: Option<String> = (None, parse_opt_string, [TRACKED],
         "the backend to use"),
+    collect_const_values: bool = (false, parse_bool, [UNTRACKED],
+        "record the value of every constant evaluated to a valtree, for tools that query \
+        them at the end of compilation (default: no)"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
         "combine CGUs into a single one"),
+    const_check_uninit: bool = (false, parse_bool, [TRACKED],
+        "reject constants whose final value contains uninitialized bytes outside of padding \
+        and unions (default: no)"),
+    const_eval_max_memory: Option<u64> = (None, parse_opt_number, [TRACKED],
+        "the maximum number of bytes a single constant evaluation may have allocated at once \
+        (default: unlimited)"),
+    const_eval_recover_ub: bool = (false, parse_bool, [UNTRACKED],
+        "allow tools to evaluate constants in a mode that continues after UB the interpreter \
+        can recover from and reports it; this never affects compilation (default: no)"),
+    const_eval_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print the number of valtrees built for type-level constants, their total number of \
+        nodes, and the largest one (default: no)"),
+    const_eval_step_granularity: usize = (1, parse_number, [TRACKED],
+        "only check the const-eval step limit every N steps; this does not change the limit, \
+        only how precisely it is enforced (default: 1)"),
+    const_eval_wrap_overflow: bool = (false, parse_bool, [UNTRACKED],
+        "allow tools to evaluate constants in a mode where arithmetic overflow wraps around and \
+        is recorded instead of panicking; this never affects compilation (default: no)"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
     debug_info_for_profiling: bool = (false, parse_bool, [TRACKED],
@@ -1444,6 +1476,12 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "enables drop tracking on MIR in generators (default: no)"),
     dual_proc_macros: bool = (false, parse_bool, [TRACKED],
         "load proc macros for both target and host, but only link to the target (default: no)"),
+    dump_const_alloc: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "after evaluating the constant or static with the given path, print the bytes, \
+        provenance and uninitialized bytes of its allocation (default: no)"),
+    dump_const_deps: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a graphviz file with the constants and statics evaluated during compilation, and \
+        which of them were evaluated while evaluating which (default: no)"),
     dump_dep_graph: bool = (false, parse_bool, [UNTRACKED],
         "dump the dependency graph to $RUST_DEP_GRAPH (default: /tmp/dep_graph.gv) \
         (default: no)"),
@@ -1502,6 +1540,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
     flatten_format_args: bool = (true, parse_bool, [TRACKED],
         "flatten nested format_args!() and literals into a simplified format_args!() call \
         (default: yes)"),
+    forbid_const_bytes: Option<Vec<u8>> = (None, parse_opt_hex_bytes, [TRACKED],
+        "emit an error if the final value of a constant or static, or memory it points to, \
+        contains the given bytes, e.g. `deadbeef` (default: no)"),
     force_unstable_if_unmarked: bool = (false, parse_bool, [TRACKED],
         "force all crates to be `rustc_private` unstable (default: no)"),
     fuel: Option<(String, u64)> = (None, parse_optimization_fuel, [TRACKED],
@@ -1673,6 +1714,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         See #77382 and #74551."),
     print_fuel: Option<String> = (None, parse_opt_string, [TRACKED],
         "make rustc print the total optimization fuel used by a crate"),
+    print_large_consts: Option<u64> = (None, parse_opt_number, [UNTRACKED],
+        "print every constant whose evaluated value takes up more than the given number of bytes, \
+        largest first"),
     print_llvm_passes: bool = (false, parse_bool, [UNTRACKED],
         "print the LLVM optimization passes being run (default: no)"),
     print_mono_items: Option<String> = (None, parse_opt_string, [UNTRACKED],
@@ -1742,6 +1786,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         for example: `-Z self-profile-events=default,query-keys`
         all options: none, all, default, generic-activity, query-provider, query-cache-hit
                      query-blocked, incr-cache-load, incr-result-hashing, query-keys, function-args, args, llvm, artifact-sizes"),
+    share_const_valtrees: bool = (false, parse_bool, [UNTRACKED],
+        "build the type-level value of constants with equal values only once, and share it \
+        (default: no)"),
     share_generics: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "make the current crate share its generic instantiations"),
     show_span: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -1813,6 +1860,12 @@ pub(crate) fn parse_dump_solver_proof_tree(
     #[rustc_lint_opt_deny_field_access("use `Session::tls_model` instead of this field")]
     tls_model: Option<TlsModel> = (None, parse_tls_model, [TRACKED],
         "choose the TLS model to use (`rustc --print tls-models` for details)"),
+    trace_const_eval: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "print every MIR statement and terminator executed while evaluating the constant with the \
+        given path, along with the locals they assign"),
+    trace_const_forcing: bool = (false, parse_bool, [UNTRACKED],
+        "print every request to evaluate a constant, and whether it was already cached \
+        (default: no)"),
     trace_macros: bool = (false, parse_bool, [UNTRACKED],
         "for every macro invocation, print its name and arguments (default: no)"),
     track_diagnostics: bool = (false, parse_bool, [UNTRACKED],
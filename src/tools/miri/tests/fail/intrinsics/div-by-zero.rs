@@ -4,6 +4,6 @@
 
 fn main() {
     unsafe {
-        let _n = unchecked_div(1i64, 0); //~ERROR: dividing by zero
+        let _n = unchecked_div(1i64, 0); //~ERROR: dividing `1_i64` by zero
     }
 }
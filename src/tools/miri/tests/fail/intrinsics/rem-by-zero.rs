@@ -4,6 +4,6 @@
 
 fn main() {
     unsafe {
-        let _n = unchecked_rem(3u32, 0); //~ ERROR: calculating the remainder with a divisor of zero
+        let _n = unchecked_rem(3u32, 0); //~ ERROR: calculating the remainder of `3_u32` with a divisor of zero
     }
 }
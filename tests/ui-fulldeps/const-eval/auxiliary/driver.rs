@@ -0,0 +1,100 @@
+//! The compiler driver shared by the tests in this directory. Each test compiles a small input
+//! crate with `run_compiler` and checks the const evaluation API it is about once analysis is
+//! done. The helpers below find the constants of the input crate.
+
+#![allow(dead_code)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_target;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def_id::DefId;
+use rustc_interface::{interface, Queries};
+use rustc_middle::mir::interpret::GlobalId;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::EarlyErrorHandler;
+use rustc_target::abi::VariantIdx;
+
+/// Writes `input` to `{name}_input.rs` and compiles it as a library crate called `input`, passing
+/// `flags` to the compiler. `test` is called once analysis is done, and compilation stops there.
+/// Returns an error if the compiler emitted any.
+pub fn run_compiler(
+    name: &str,
+    input: &str,
+    flags: &[&str],
+    test: impl FnMut(TyCtxt<'_>) + Send,
+) -> interface::Result<()> {
+    let path = format!("{name}_input.rs");
+    std::fs::write(&path, input).unwrap();
+    let mut args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        "input".to_string(),
+        path,
+    ];
+    args.extend(flags.iter().map(|flag| flag.to_string()));
+    let mut callbacks = AfterAnalysis { test };
+    rustc_driver::catch_fatal_errors(|| RunCompiler::new(&args, &mut callbacks).run())
+        .and_then(|result| result)
+}
+
+struct AfterAnalysis<F> {
+    test: F,
+}
+
+impl<F: FnMut(TyCtxt<'_>) + Send> Callbacks for AfterAnalysis<F> {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| (self.test)(tcx));
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+/// Returns the local constant called `name`.
+pub fn local_const(tcx: TyCtxt<'_>, name: &str) -> DefId {
+    tcx.hir()
+        .body_owners()
+        .map(|def_id| def_id.to_def_id())
+        .find(|&def_id| tcx.def_path_str(def_id) == name)
+        .unwrap_or_else(|| panic!("no constant called `{name}`"))
+}
+
+/// Returns the local constant called `name` as it would appear in a type.
+pub fn unevaluated_const<'tcx>(tcx: TyCtxt<'tcx>, name: &str) -> ty::Const<'tcx> {
+    let def_id = local_const(tcx, name);
+    let uv = ty::UnevaluatedConst::new(def_id, ty::List::empty());
+    ty::Const::new_unevaluated(tcx, uv, tcx.type_of(def_id).subst_identity())
+}
+
+/// Returns the local associated constant called `name`.
+pub fn assoc_const(tcx: TyCtxt<'_>, name: &str) -> DefId {
+    tcx.hir()
+        .body_owners()
+        .map(|def_id| def_id.to_def_id())
+        .find(|&def_id| tcx.def_path_str(def_id).ends_with(&format!("::{name}")))
+        .unwrap_or_else(|| panic!("no associated constant called `{name}`"))
+}
+
+/// Returns the associated constant `name` of `Add<N>`, a type with a `u8` const parameter.
+pub fn add_const<'tcx>(tcx: TyCtxt<'tcx>, name: &str, n: u8) -> GlobalId<'tcx> {
+    let def_id = assoc_const(tcx, name);
+    let n = ty::Const::from_bits(tcx, n.into(), ty::ParamEnv::empty().and(tcx.types.u8));
+    GlobalId { instance: ty::Instance::new(def_id, tcx.mk_substs(&[n.into()])), promoted: None }
+}
+
+/// Returns the index of the variant called `name` of the enum type of `ct`.
+pub fn variant_idx(ct: ty::Const<'_>, name: &str) -> VariantIdx {
+    let ty::Adt(def, _) = ct.ty().kind() else { panic!("{ct:?} isn't an enum") };
+    let (idx, _) = def.variants().iter_enumerated().find(|(_, v)| v.name.as_str() == name).unwrap();
+    idx
+}
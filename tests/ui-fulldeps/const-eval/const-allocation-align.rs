@@ -0,0 +1,43 @@
+// run-pass
+// Test that `const_allocation_align` returns the alignment of evaluated constants.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::local_const;
+use rustc_middle::mir::interpret::GlobalId;
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks that the `const_allocation_align` query returns the alignment of constant values.
+fn test_const_allocation_align(tcx: TyCtxt<'_>) {
+    let align = |name| {
+        let instance = ty::Instance::mono(tcx, local_const(tcx, name));
+        let cid = GlobalId { instance, promoted: None };
+        let key = ty::ParamEnv::reveal_all().with_const().and(cid);
+        tcx.const_allocation_align(key).map(|align| align.bytes())
+    };
+    assert_eq!(align("ALIGNED"), Some(64));
+    assert_eq!(align("SCALAR"), Some(2));
+}
+
+fn main() {
+    driver::run_compiler("const_allocation_align", INPUT, &[], test_const_allocation_align)
+        .unwrap();
+}
+
+const INPUT: &str = r#"
+#[repr(align(64))]
+pub struct Aligned(pub u8);
+
+pub const ALIGNED: Aligned = Aligned(1);
+pub const SCALAR: u16 = 2;
+"#;
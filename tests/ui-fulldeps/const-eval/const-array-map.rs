@@ -0,0 +1,73 @@
+// run-pass
+// Test that `eval_const_array_map` agrees with the interpreter and rejects non-const functions.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_const_eval;
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::{local_const, unevaluated_const};
+use rustc_const_eval::const_eval::eval_const_array_map;
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks that `eval_const_array_map` computes the same array as the interpreter, and that it
+/// gives up on functions that aren't const.
+fn test_eval_const_array_map(tcx: TyCtxt<'_>) {
+    let param_env = ty::ParamEnv::reveal_all();
+    let array = unevaluated_const(tcx, "ARR");
+    let fn_ty = |name| tcx.type_of(local_const(tcx, name)).subst_identity();
+
+    // `EXPECTED` applies `double` to each element in a loop run by the interpreter.
+    let mapped = eval_const_array_map(tcx, param_env, array, fn_ty("double")).unwrap();
+    let expected = unevaluated_const(tcx, "EXPECTED").eval(tcx, param_env);
+    assert!(matches!(expected.kind(), ty::ConstKind::Value(_)));
+    assert_eq!(mapped, expected);
+
+    // Functions and closures that can't be called in constants are rejected.
+    assert_eq!(eval_const_array_map(tcx, param_env, array, fn_ty("not_const")), None);
+    let closure = tcx
+        .hir()
+        .body_owners()
+        .find(|&def_id| tcx.is_closure(def_id.to_def_id()))
+        .expect("no closure in the input");
+    let closure_ty = tcx.type_of(closure).subst_identity();
+    assert_eq!(eval_const_array_map(tcx, param_env, array, closure_ty), None);
+}
+
+fn main() {
+    driver::run_compiler("const_array_map", INPUT, &[], test_eval_const_array_map).unwrap();
+}
+
+const INPUT: &str = r#"
+pub const ARR: [u32; 4] = [1, 2, 3, 4];
+
+pub const fn double(x: u32) -> u32 {
+    x * 2
+}
+
+pub fn not_const(x: u32) -> u32 {
+    x + 1
+}
+
+pub const EXPECTED: [u32; 4] = {
+    let mut out = ARR;
+    let mut i = 0;
+    while i < out.len() {
+        out[i] = double(out[i]);
+        i += 1;
+    }
+    out
+};
+
+pub fn closure() -> impl Fn(u32) -> u32 {
+    |x| x + 1
+}
+"#;
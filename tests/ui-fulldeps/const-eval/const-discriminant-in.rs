@@ -0,0 +1,48 @@
+// run-pass
+// Test that `Const::discriminant_in` checks the variant of a constant against a set.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::{local_const, unevaluated_const, variant_idx};
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks that `Const::discriminant_in` tests the active variant against a set of variants.
+fn test_discriminant_in(tcx: TyCtxt<'_>) {
+    let param_env = ty::ParamEnv::reveal_all();
+    let north = unevaluated_const(tcx, "NORTH");
+    let set = [variant_idx(north, "North"), variant_idx(north, "East")];
+    assert_eq!(north.discriminant_in(tcx, param_env, &set), Some(true));
+    assert_eq!(unevaluated_const(tcx, "EAST").discriminant_in(tcx, param_env, &set), Some(true));
+    assert_eq!(unevaluated_const(tcx, "SOUTH").discriminant_in(tcx, param_env, &set), Some(false));
+    assert_eq!(north.discriminant_in(tcx, param_env, &[]), Some(false));
+
+    // Only enums have variants.
+    assert_eq!(unevaluated_const(tcx, "NUM").discriminant_in(tcx, param_env, &set), None);
+}
+
+fn main() {
+    driver::run_compiler("const_discriminant_in", INPUT, &[], test_discriminant_in).unwrap();
+}
+
+const INPUT: &str = r#"
+pub enum Dir {
+    North,
+    East,
+    South,
+}
+
+pub const NORTH: Dir = Dir::North;
+pub const EAST: Dir = Dir::East;
+pub const SOUTH: Dir = Dir::South;
+pub const NUM: u8 = 0;
+"#;
@@ -0,0 +1,64 @@
+// run-pass
+// Test that `eval_recovering_ub` reports recovered UB only with `-Z const-eval-recover-ub`.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_const_eval;
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::add_const;
+use rustc_const_eval::const_eval::eval_recovering_ub;
+use rustc_middle::mir::interpret::InterpError;
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks that `eval_recovering_ub` collects the UB that evaluation continued after, only with
+/// `-Z const-eval-recover-ub`, and that the flag doesn't change normal evaluation.
+fn test_eval_recovering_ub(tcx: TyCtxt<'_>, recover_ub: bool) {
+    let param_env = ty::ParamEnv::reveal_all();
+    let (fine, ub) = (add_const(tcx, "ONE", 1), add_const(tcx, "ONE", 255));
+
+    let value = tcx.const_eval_global_id(param_env, fine, None).unwrap();
+    assert_eq!(value.try_to_scalar_int().unwrap().try_to_u8(), Ok(2));
+    assert!(tcx.const_eval_global_id(param_env, ub, None).is_err());
+
+    let reports = [fine, ub].map(|cid| eval_recovering_ub(tcx, param_env.and(cid)));
+    if !recover_ub {
+        assert!(reports.iter().all(Option::is_none));
+        return;
+    }
+    let [fine, ub] = reports.map(Option::unwrap);
+    assert!(fine.recovered.is_empty());
+    assert!(fine.error.is_none());
+    assert_eq!(ub.recovered.len(), 1);
+    assert!(matches!(ub.recovered[0].error, InterpError::UndefinedBehavior(_)));
+    assert!(ub.error.is_none());
+}
+
+fn main() {
+    for recover_ub in [false, true] {
+        let flags: &[&str] = if recover_ub { &["-Zconst-eval-recover-ub"] } else { &[] };
+        // Evaluating the constant with UB the normal way emits an error.
+        let result = driver::run_compiler("const_eval_recover_ub", INPUT, flags, |tcx| {
+            test_eval_recovering_ub(tcx, recover_ub)
+        });
+        assert!(result.is_err());
+    }
+}
+
+const INPUT: &str = r#"
+#![feature(core_intrinsics, const_int_unchecked_arith)]
+
+pub struct Add<const N: u8>;
+
+impl<const N: u8> Add<N> {
+    pub const ONE: u8 = unsafe { std::intrinsics::unchecked_add(N, 1) };
+}
+"#;
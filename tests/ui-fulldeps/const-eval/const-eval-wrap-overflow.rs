@@ -0,0 +1,68 @@
+// run-pass
+// Test that `eval_wrapping_overflow` wraps on overflow only with `-Z const-eval-wrap-overflow`.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_const_eval;
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::add_const;
+use rustc_const_eval::const_eval::eval_wrapping_overflow;
+use rustc_middle::mir::interpret::ConstAlloc;
+use rustc_middle::mir::{AssertKind, BinOp};
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks that `eval_wrapping_overflow` continues with the wrapped result of overflowing
+/// arithmetic and records where that happened, only with `-Z const-eval-wrap-overflow`, and that
+/// the flag doesn't change normal evaluation.
+fn test_eval_wrapping_overflow(tcx: TyCtxt<'_>, wrap_overflow: bool) {
+    let param_env = ty::ParamEnv::reveal_all();
+    let (fine, overflow) = (add_const(tcx, "DOUBLE", 100), add_const(tcx, "DOUBLE", 200));
+
+    let value = tcx.const_eval_global_id(param_env, fine, None).unwrap();
+    assert_eq!(value.try_to_scalar_int().unwrap().try_to_u8(), Ok(200));
+    assert!(tcx.const_eval_global_id(param_env, overflow, None).is_err());
+
+    let reports = [fine, overflow].map(|cid| eval_wrapping_overflow(tcx, param_env.and(cid)));
+    if !wrap_overflow {
+        assert!(reports.iter().all(Option::is_none));
+        return;
+    }
+    let [fine, overflow] = reports.map(Option::unwrap);
+    assert!(fine.overflows.is_empty());
+    assert_eq!(overflow.overflows.len(), 1);
+    assert!(matches!(overflow.overflows[0].kind, AssertKind::Overflow(BinOp::Mul, ..)));
+    let value = |alloc: ConstAlloc<'_>| {
+        let alloc = tcx.global_alloc(alloc.alloc_id).unwrap_memory();
+        alloc.inner().inspect_with_uninit_and_ptr_outside_interpreter(0..1)[0]
+    };
+    assert_eq!(value(fine.result.unwrap()), 200);
+    assert_eq!(value(overflow.result.unwrap()), 200u8.wrapping_mul(2));
+}
+
+fn main() {
+    for wrap_overflow in [false, true] {
+        let flags: &[&str] = if wrap_overflow { &["-Zconst-eval-wrap-overflow"] } else { &[] };
+        // Evaluating the constant that overflows the normal way emits an error.
+        let result = driver::run_compiler("const_eval_wrap_overflow", INPUT, flags, |tcx| {
+            test_eval_wrapping_overflow(tcx, wrap_overflow)
+        });
+        assert!(result.is_err());
+    }
+}
+
+const INPUT: &str = r#"
+pub struct Add<const N: u8>;
+
+impl<const N: u8> Add<N> {
+    pub const DOUBLE: u8 = N * 2;
+}
+"#;
@@ -0,0 +1,60 @@
+// run-pass
+// Test that `eval_const_fn_call` passes the given caller location to `#[track_caller]` functions.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_const_eval;
+extern crate rustc_middle;
+extern crate rustc_span;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::local_const;
+use rustc_const_eval::const_eval::eval_const_fn_call;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::Span;
+
+/// Checks that a `#[track_caller]` const fn evaluated with `eval_const_fn_call` sees the given
+/// caller location, or its own definition without one.
+fn test_eval_const_fn_call_caller(tcx: TyCtxt<'_>) {
+    let param_env = ty::ParamEnv::reveal_all();
+    let caller_line = local_const(tcx, "caller_line");
+    let line_of = |span: Span| tcx.sess.source_map().lookup_char_pos(span.lo()).line as u32;
+    let eval = |caller| -> u32 {
+        let ct = eval_const_fn_call(tcx, param_env, caller_line, ty::List::empty(), &[], caller)
+            .unwrap();
+        ct.eval_bits(tcx, param_env, tcx.types.u32) as u32
+    };
+
+    let use_site = tcx.def_span(local_const(tcx, "USE_SITE"));
+    assert_eq!(eval(Some(use_site)), line_of(use_site));
+    assert_eq!(eval(None), line_of(tcx.def_span(caller_line)));
+    assert_ne!(line_of(use_site), line_of(tcx.def_span(caller_line)));
+}
+
+fn main() {
+    driver::run_compiler(
+        "const_fn_call_caller_location",
+        INPUT,
+        &[],
+        test_eval_const_fn_call_caller,
+    )
+    .unwrap();
+}
+
+const INPUT: &str = r#"
+#![feature(const_caller_location, const_location_fields)]
+
+#[track_caller]
+pub const fn caller_line() -> u32 {
+    std::panic::Location::caller().line()
+}
+
+pub const USE_SITE: () = ();
+"#;
@@ -0,0 +1,62 @@
+// run-pass
+// Test that `Const::from_const_value` and `valtree_to_const_val` round-trip constant values.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_const_eval;
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::local_const;
+use rustc_const_eval::const_eval::testing::{eval_to_valtree_for_test, EvalForTestError};
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks that the values of constants survive the trip from `ConstValue` to valtree and back.
+fn test_from_const_value(tcx: TyCtxt<'_>) {
+    for name in ["STRUCT", "ARRAY", "STR"] {
+        let def_id = local_const(tcx, name);
+        let ty = tcx.type_of(def_id).subst_identity();
+        let val = tcx.const_eval_poly(def_id).unwrap();
+        let ct = ty::Const::from_const_value(tcx, val, ty).unwrap();
+        assert_eq!(ct.ty(), ty);
+
+        // The valtree is the same as the one the type system computes for the constant.
+        let ty::ConstKind::Value(valtree) = ct.kind() else { panic!("{name} is {ct:?}") };
+        assert_eq!(eval_to_valtree_for_test(tcx, def_id), Ok(valtree));
+
+        // Going back to a `ConstValue` and again to a `Const` gives the same constant.
+        let val = tcx.valtree_to_const_val((ty, valtree));
+        assert_eq!(ty::Const::from_const_value(tcx, val, ty), Some(ct));
+    }
+
+    // Raw pointers have no valtree representation.
+    let def_id = local_const(tcx, "RAW_PTR");
+    let val = tcx.const_eval_poly(def_id).unwrap();
+    let ty = tcx.type_of(def_id).subst_identity();
+    assert_eq!(ty::Const::from_const_value(tcx, val, ty), None);
+    assert_eq!(eval_to_valtree_for_test(tcx, def_id), Err(EvalForTestError::NoValTree));
+}
+
+fn main() {
+    driver::run_compiler("const_from_const_value", INPUT, &[], test_from_const_value).unwrap();
+}
+
+const INPUT: &str = r#"
+#[derive(PartialEq, Eq)]
+pub struct Point {
+    pub x: i32,
+    pub y: (u8, bool),
+}
+
+pub const STRUCT: Point = Point { x: -3, y: (7, true) };
+pub const ARRAY: [u16; 3] = [1, 2, 3];
+pub const STR: &str = "hello";
+pub const RAW_PTR: *const u8 = &5;
+"#;
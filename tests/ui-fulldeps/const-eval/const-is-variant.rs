@@ -0,0 +1,46 @@
+// run-pass
+// Test that `Const::is_variant` reads the variant of `Some(3)` and `None`.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::{local_const, unevaluated_const, variant_idx};
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks that `Const::is_variant` reads the active variant of constant enums.
+fn test_is_variant(tcx: TyCtxt<'_>) {
+    let param_env = ty::ParamEnv::reveal_all();
+    for (some, none) in [("SOME", "NONE"), ("SOME_REF", "NONE_REF")] {
+        let (some, none) = (unevaluated_const(tcx, some), unevaluated_const(tcx, none));
+        let (some_idx, none_idx) = (variant_idx(some, "Some"), variant_idx(some, "None"));
+        assert_eq!(some.is_variant(tcx, param_env, some_idx), Some(true));
+        assert_eq!(some.is_variant(tcx, param_env, none_idx), Some(false));
+        assert_eq!(none.is_variant(tcx, param_env, some_idx), Some(false));
+        assert_eq!(none.is_variant(tcx, param_env, none_idx), Some(true));
+    }
+
+    // Only enums have variants.
+    let some_idx = variant_idx(unevaluated_const(tcx, "SOME"), "Some");
+    assert_eq!(unevaluated_const(tcx, "NUM").is_variant(tcx, param_env, some_idx), None);
+}
+
+fn main() {
+    driver::run_compiler("const_is_variant", INPUT, &[], test_is_variant).unwrap();
+}
+
+const INPUT: &str = r#"
+pub const SOME: Option<i32> = Some(3);
+pub const NONE: Option<i32> = None;
+pub const SOME_REF: Option<&u8> = Some(&3);
+pub const NONE_REF: Option<&u8> = None;
+pub const NUM: i32 = 3;
+"#;
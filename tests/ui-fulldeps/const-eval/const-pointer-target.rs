@@ -0,0 +1,44 @@
+// run-pass
+// Test that `Const::try_eval_pointer_target` returns the allocation and offset of a pointer.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::{local_const, unevaluated_const};
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks that `Const::try_eval_pointer_target` finds where pointer constants point to.
+fn test_try_eval_pointer_target(tcx: TyCtxt<'_>) {
+    let param_env = ty::ParamEnv::reveal_all();
+
+    // `&ARR[2]` points two elements into a copy of `ARR`.
+    let (alloc_id, offset) =
+        unevaluated_const(tcx, "THIRD").try_eval_pointer_target(tcx, param_env).unwrap();
+    assert_eq!(offset.bytes(), 2 * 4);
+    let alloc = tcx.global_alloc(alloc_id).unwrap_memory();
+    assert_eq!(alloc.inner().size().bytes(), 4 * 4);
+
+    // Null pointers and integers point nowhere.
+    assert_eq!(unevaluated_const(tcx, "NULL").try_eval_pointer_target(tcx, param_env), None);
+    assert_eq!(unevaluated_const(tcx, "NUM").try_eval_pointer_target(tcx, param_env), None);
+}
+
+fn main() {
+    driver::run_compiler("const_pointer_target", INPUT, &[], test_try_eval_pointer_target).unwrap();
+}
+
+const INPUT: &str = r#"
+pub const ARR: [u32; 4] = [1, 2, 3, 4];
+pub const THIRD: &u32 = &ARR[2];
+pub const NULL: *const u32 = std::ptr::null();
+pub const NUM: usize = 8;
+"#;
@@ -0,0 +1,84 @@
+// run-pass
+// Test that the `const_shape` query classifies constants by their type without evaluating them.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::{assoc_const, local_const};
+use rustc_middle::mir::interpret::{ConstShape, ErrorHandled, EvalToConstShapeResult, GlobalId};
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Returns the shape of the associated constant `name` of `Wrapper<T>`, for `T = u8` or for a
+/// generic `T`.
+fn wrapper_const_shape(tcx: TyCtxt<'_>, name: &str, generic: bool) -> EvalToConstShapeResult {
+    let def_id = assoc_const(tcx, name);
+    let (param_env, substs) = if generic {
+        (tcx.param_env(def_id), ty::InternalSubsts::identity_for_item(tcx, def_id))
+    } else {
+        (ty::ParamEnv::reveal_all(), tcx.mk_substs(&[tcx.types.u8.into()]))
+    };
+    let cid = GlobalId { instance: ty::Instance::new(def_id, substs), promoted: None };
+    tcx.const_shape(param_env.and(cid))
+}
+
+/// Checks that the `const_shape` query classifies constants by their type.
+fn test_const_shape(tcx: TyCtxt<'_>) {
+    let shape = |name| {
+        let instance = ty::Instance::mono(tcx, local_const(tcx, name));
+        tcx.const_shape(ty::ParamEnv::reveal_all().and(GlobalId { instance, promoted: None }))
+    };
+    assert_eq!(shape("SCALAR"), Ok(ConstShape::Scalar));
+    assert_eq!(shape("REF"), Ok(ConstShape::Pointer));
+    assert_eq!(shape("FN_PTR"), Ok(ConstShape::Pointer));
+    assert_eq!(shape("ARRAY"), Ok(ConstShape::Array));
+    assert_eq!(shape("STRUCT"), Ok(ConstShape::Aggregate));
+    assert_eq!(shape("TUPLE"), Ok(ConstShape::Aggregate));
+
+    // The constant isn't evaluated, so this doesn't report the panic.
+    assert_eq!(wrapper_const_shape(tcx, "PANICS", false), Ok(ConstShape::Array));
+    // Constants whose type is a generic parameter can only be classified once it is known.
+    assert_eq!(wrapper_const_shape(tcx, "VALUE", false), Ok(ConstShape::Scalar));
+    assert_eq!(wrapper_const_shape(tcx, "VALUE", true), Err(ErrorHandled::TooGeneric));
+}
+
+fn main() {
+    driver::run_compiler("const_shape", INPUT, &[], test_const_shape).unwrap();
+}
+
+const INPUT: &str = r#"
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub trait Zero {
+    const ZERO: Self;
+}
+
+impl Zero for u8 {
+    const ZERO: u8 = 0;
+}
+
+pub struct Wrapper<T>(pub T);
+
+impl<T: Zero> Wrapper<T> {
+    pub const PANICS: [u8; 4] = panic!();
+    pub const VALUE: T = T::ZERO;
+}
+
+pub const SCALAR: u32 = 3;
+pub const REF: &str = "hello";
+pub const FN_PTR: fn() = || {};
+pub const ARRAY: [u8; 2] = [1, 2];
+pub const STRUCT: Point = Point { x: 1, y: 2 };
+pub const TUPLE: (u8, bool) = (1, true);
+"#;
@@ -0,0 +1,63 @@
+// run-pass
+// Test that `-Z share-const-valtrees` builds one valtree for constants with equal values.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_const_eval;
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::local_const;
+use rustc_const_eval::const_eval::testing::eval_to_valtree_for_test;
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Returns the branches of the valtree of the local constant called `name`.
+fn valtree_branches<'tcx>(tcx: TyCtxt<'tcx>, name: &str) -> &'tcx [ty::ValTree<'tcx>] {
+    let valtree = eval_to_valtree_for_test(tcx, local_const(tcx, name));
+    match valtree {
+        Ok(ty::ValTree::Branch(branches)) => branches,
+        _ => panic!("`{name}` has no valtree with branches: {valtree:?}"),
+    }
+}
+
+/// Checks that constants with equal values share their valtree, and others don't.
+fn test_shared_valtree(tcx: TyCtxt<'_>) {
+    let literal = valtree_branches(tcx, "LITERAL");
+    let computed = valtree_branches(tcx, "COMPUTED");
+    let reversed = valtree_branches(tcx, "REVERSED");
+    assert_eq!(literal, computed);
+    assert!(std::ptr::eq(literal, computed));
+    assert_ne!(literal, reversed);
+    assert!(!std::ptr::eq(literal, reversed));
+}
+
+fn main() {
+    driver::run_compiler(
+        "const_shared_valtree",
+        INPUT,
+        &["-Zshare-const-valtrees"],
+        test_shared_valtree,
+    )
+    .unwrap();
+}
+
+const INPUT: &str = r#"
+pub const LITERAL: [u16; 3] = [1, 2, 3];
+pub const COMPUTED: [u16; 3] = {
+    let mut array = [0; 3];
+    let mut i = 0;
+    while i < array.len() {
+        array[i] = i as u16 + 1;
+        i += 1;
+    }
+    array
+};
+pub const REVERSED: [u16; 3] = [3, 2, 1];
+"#;
@@ -0,0 +1,59 @@
+// run-pass
+// Test that `Const::try_eval_variant_name` handles enums with explicit discriminants.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+
+#[path = "auxiliary/driver.rs"]
+mod driver;
+
+use driver::{local_const, unevaluated_const};
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks that `Const::try_eval_variant_name` maps explicit discriminants to the right variants.
+fn test_try_eval_variant_name(tcx: TyCtxt<'_>) {
+    let param_env = ty::ParamEnv::reveal_all();
+    let name = |ct| {
+        let name = unevaluated_const(tcx, ct).try_eval_variant_name(tcx, param_env);
+        name.map(|name| name.to_string())
+    };
+    assert_eq!(name("RED").as_deref(), Some("Red"));
+    assert_eq!(name("GREEN").as_deref(), Some("Green"));
+    assert_eq!(name("BLUE").as_deref(), Some("Blue"));
+    assert_eq!(name("MINUS").as_deref(), Some("Minus"));
+    assert_eq!(name("PLUS").as_deref(), Some("Plus"));
+
+    // Only enums have variants.
+    assert_eq!(name("NUM"), None);
+}
+
+fn main() {
+    driver::run_compiler("const_variant_name", INPUT, &[], test_try_eval_variant_name).unwrap();
+}
+
+const INPUT: &str = r#"
+pub enum Color {
+    Red = 10,
+    Green = 2,
+    Blue = 7,
+}
+
+#[repr(i8)]
+pub enum Sign {
+    Minus = -1,
+    Plus = 1,
+}
+
+pub const RED: Color = Color::Red;
+pub const GREEN: Color = Color::Green;
+pub const BLUE: Color = Color::Blue;
+pub const MINUS: Sign = Sign::Minus;
+pub const PLUS: Sign = Sign::Plus;
+pub const NUM: i32 = 10;
+"#;
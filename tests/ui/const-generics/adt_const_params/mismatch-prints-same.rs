@@ -0,0 +1,14 @@
+// Check that we explain mismatches between const arguments that print the same.
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+struct ConstStr<const S: &'static str>;
+
+const A: &str = unsafe { std::str::from_utf8_unchecked(&[0xFF]) };
+const B: &str = unsafe { std::str::from_utf8_unchecked(&[0xFE]) };
+
+fn main() {
+    let mut s = ConstStr::<A>;
+    s = ConstStr::<B>;
+    //~^ ERROR mismatched types
+}
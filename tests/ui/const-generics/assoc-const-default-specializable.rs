@@ -0,0 +1,19 @@
+// check-pass
+// `default` associated consts can't be resolved before all impls are revealed, so evaluating
+// them with monomorphic substs must be retried later instead of failing.
+
+#![feature(specialization)] //~ WARN the feature `specialization` is incomplete
+
+trait Assoc {
+    const N: usize;
+}
+
+impl<T> Assoc for T {
+    default const N: usize = 3;
+}
+
+const N: usize = <u32 as Assoc>::N;
+
+fn main() {
+    let _ = N;
+}
@@ -0,0 +1,27 @@
+// check-pass
+// Associated consts of concrete types must be resolved to a value even if they come from a
+// blanket impl, both in const arguments and array lengths.
+
+trait Marker {}
+impl Marker for u8 {}
+
+trait Assoc {
+    const N: usize;
+}
+
+impl<T: Marker> Assoc for T {
+    const N: usize = 3;
+}
+
+struct Arr<const N: usize>;
+
+fn take(_: Arr<3>) {}
+
+fn generic<T>() -> [T; <u8 as Assoc>::N] {
+    loop {}
+}
+
+fn main() {
+    take(Arr::<{ <u8 as Assoc>::N }>);
+    let _: fn() -> [u32; 3] = generic::<u32>;
+}
@@ -0,0 +1,35 @@
+// Test that `non_canonical_const_generic` only fires for const generic arguments computing a
+// small integer from literals.
+
+#![deny(non_canonical_const_generic)]
+
+pub struct Buf<const N: usize>([u8; N]);
+pub struct Offset<const N: i32>;
+
+pub fn four() -> Buf<{ 2 + 2 }> {
+    //~^ ERROR const generic argument is computed from literals
+    Buf([0; 4])
+}
+
+pub fn minus_one() -> Offset<{ 1 - 2 }> {
+    //~^ ERROR const generic argument is computed from literals
+    Offset
+}
+
+pub fn literal() -> Buf<4> {
+    Buf([0; 4])
+}
+
+pub fn braced_literal() -> Offset<{ -1 }> {
+    Offset
+}
+
+pub fn large() -> Buf<{ 1000 + 1 }> {
+    Buf([0; 1001])
+}
+
+pub fn array_length() -> [u8; 2 + 2] {
+    [0; 2 + 2]
+}
+
+fn main() {}
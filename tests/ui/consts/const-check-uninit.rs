@@ -0,0 +1,22 @@
+// compile-flags: -Zconst-check-uninit
+use std::mem::MaybeUninit;
+
+#[repr(C)]
+struct Padded {
+    a: u8,
+    b: u16,
+}
+
+// Padding bytes are never looked at.
+const PADDED: Padded = Padded { a: 1, b: 2 };
+
+// Neither are the contents of unions.
+const UNION: MaybeUninit<u32> = MaybeUninit::uninit();
+
+const UNINIT: u32 = unsafe { MaybeUninit::uninit().assume_init() };
+//~^ ERROR encountered uninitialized bytes of type `u32` in final constant
+
+const BEHIND_REF: &(u8, u32) = &(1, unsafe { MaybeUninit::uninit().assume_init() });
+//~^ ERROR encountered uninitialized bytes of type `u32` in final constant
+
+fn main() {}
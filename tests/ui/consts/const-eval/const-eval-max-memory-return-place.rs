@@ -0,0 +1,11 @@
+// compile-flags: -Z const-eval-max-memory=1024
+
+// The memory limit also covers the place the constant's value is written to, which is allocated
+// before the constant's body starts running.
+
+const SMALL: [u8; 512] = [0; 512];
+
+const BIG: [u8; 4096] = [0; 4096];
+//~^ ERROR evaluation of constant value failed
+
+fn main() {}
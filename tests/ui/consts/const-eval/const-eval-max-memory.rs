@@ -0,0 +1,14 @@
+// compile-flags: -Z const-eval-max-memory=1024
+
+const SMALL: usize = {
+    let a = [0u8; 512];
+    a.len()
+};
+
+const BIG: usize = {
+    let a = [0u8; 4096];
+    //~^ ERROR evaluation of constant value failed
+    a.len()
+};
+
+fn main() {}
@@ -0,0 +1,22 @@
+// Check that an `assert!`-style panic in a `#[track_caller]` const fn reports the location of the
+// constant that called it, not a location inside the function.
+#![crate_type = "lib"]
+
+#[track_caller]
+const fn check(ok: bool) {
+    if !ok {
+        panic!("check failed");
+    }
+}
+
+#[track_caller]
+const fn check_nonzero(x: u32) -> u32 {
+    check(x != 0);
+    x
+}
+
+const ONE: u32 = check_nonzero(1);
+
+const ZERO: u32 = check_nonzero(0);
+//~^ ERROR evaluation of constant value failed
+//~| NOTE the evaluated program panicked at 'check failed'
@@ -0,0 +1,14 @@
+#![feature(core_intrinsics)]
+// Division by zero reports the dividend, also when the division is not checked in MIR.
+
+const A: i32 = 42;
+
+const DIV: i32 = unsafe { std::intrinsics::unchecked_div(A, 0) };
+//~^ ERROR evaluation of constant value failed
+//~| dividing `42_i32` by zero
+
+const REM: u8 = unsafe { std::intrinsics::unchecked_rem(7, 0) };
+//~^ ERROR evaluation of constant value failed
+//~| calculating the remainder of `7_u8` with a divisor of zero
+
+fn main() {}
@@ -0,0 +1,11 @@
+// compile-flags: -Zdump-const-alloc=TABLE
+// build-pass
+// ignore-endian-big
+// normalize-stderr-test "alloc[0-9]+" -> "ALLOC_ID"
+
+const TABLE: [u16; 3] = [1, 0x0203, 0x4142];
+const OTHER: u32 = 7;
+
+fn main() {
+    let _ = (TABLE, OTHER);
+}
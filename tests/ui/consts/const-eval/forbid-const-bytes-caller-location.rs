@@ -0,0 +1,16 @@
+// build-pass
+// compile-flags: -Z forbid-const-bytes=2f
+
+// Regression test for an ICE: the caller location allocated for `Location::caller()` contains
+// the file name, but isn't the value of a constant and must not be scanned.
+
+use std::panic::Location;
+
+#[track_caller]
+fn caller() -> &'static Location<'static> {
+    Location::caller()
+}
+
+fn main() {
+    let _ = caller();
+}
@@ -0,0 +1,14 @@
+// compile-flags: -Z forbid-const-bytes=deadbeef
+// normalize-stderr-test "alloc[0-9]+" -> "ALLOC_ID"
+
+const ARRAY: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
+//~^ ERROR the final value of `ARRAY` contains the forbidden bytes `deadbeef`
+
+const BEHIND_REF: &[u8] = b"\x00\xde\xad\xbe\xef";
+//~^ ERROR the final value of `BEHIND_REF` contains the forbidden bytes `deadbeef`
+
+// Only complete occurrences within a single allocation are rejected.
+const PREFIX: [u8; 3] = [0xde, 0xad, 0xbe];
+const SPLIT: (&[u8], &[u8]) = (b"\xde\xad", b"\xbe\xef");
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Test that a misaligned read repeated in a loop is reported once, with a note saying how often
+// it happened.
+
+const SUM: u32 = unsafe {
+    let bytes = [0u8; 8];
+    let ptr = bytes.as_ptr().cast::<u32>();
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 4 {
+        let value = *ptr;
+        //~^ ERROR accessing memory with alignment 1, but alignment 4 is required
+        //~| WARN this was previously accepted by the compiler
+        sum += value;
+        i += 1;
+    }
+    sum
+};
+
+fn main() {
+    let _ = SUM;
+}
@@ -0,0 +1,19 @@
+// check-fail
+// compile-flags: -Z tiny-const-eval-limit -Z const-eval-step-granularity=8
+
+// Batching up steps must not hide the limit being passed.
+const fn simple_loop(n: u32) -> u32 {
+    let mut index = 0;
+    while index < n {
+        //~^ ERROR is taking a long time
+        index = index + 1;
+    }
+    0
+}
+
+const X: u32 = simple_loop(5);
+const Y: u32 = simple_loop(100);
+
+fn main() {
+    println!("{X} {Y}");
+}
@@ -0,0 +1,20 @@
+// check-pass
+// only-64bit
+// compile-flags: -Z print-large-consts=16
+#![crate_type = "lib"]
+
+pub const SMALL: [u8; 16] = [0; 16];
+pub const BIG: [u64; 8] = [0; 8];
+// The pointee is counted along with the pointer itself.
+pub const BYTES: &[u8; 32] = &[1; 32];
+
+pub struct S;
+
+impl S {
+    pub const TABLE: [u16; 20] = [0; 20];
+}
+
+pub trait Generic {
+    // Not evaluated, as it depends on `Self`.
+    const TABLE: [u8; 100] = [0; 100];
+}
@@ -0,0 +1,29 @@
+// build-pass
+// Test that `repeated_inline_const_eval` fires once an inline constant has been evaluated for
+// more than 64 instances of the function it is part of.
+
+#![feature(inline_const)]
+#![warn(repeated_inline_const_eval)]
+
+fn table<const N: usize>() -> u32 {
+    const { 7 } //~ WARN evaluated separately for more than 64 instances
+}
+
+fn once() -> u32 {
+    const { 7 }
+}
+
+macro_rules! call_table {
+    ($($n:literal)*) => {
+        $(table::<$n>();)*
+    };
+}
+
+fn main() {
+    call_table!(
+        0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+        32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60
+        61 62 63 64
+    );
+    once();
+}